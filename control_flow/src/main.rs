@@ -83,6 +83,25 @@ fn return_values_from_loops() {
     println!("The result of the loop is: {}", result);
 }
 
+// The retrying described above can be written once and reused. `retry_until` calls `f` until it
+// returns `Some`, and the `loop` breaks with that value. We give up after `max` calls and break with
+// `None`, so with a `max` of 0 `f` is never called at all.
+#[allow(dead_code)]
+fn retry_until<T, F: FnMut() -> Option<T>>(mut f: F, max: usize) -> Option<T> {
+    let mut attempts = 0;
+
+    loop {
+        if attempts == max {
+            break None;
+        }
+        attempts += 1;
+
+        if let Some(value) = f() {
+            break Some(value);
+        }
+    }
+}
+
 // It's often useful for a program to evaluate a condition within a loop. While the condition is true
 // the loop runs. When condition ceases to be true, the program calls `break`, stopping the loop.
 // This loop type could be implemented using a combination of `loop`, `if`, `else` and `break`, but
@@ -116,3 +135,116 @@ fn looping_range_with_for() {
     }
     println!("LIFTOFF!!!");
 }
+
+// FizzBuzz puts `for` over a range and `if`/`else if` together: each number from 1 to `n` becomes
+// "Fizz" if it's divisible by 3, "Buzz" if it's divisible by 5, "FizzBuzz" if it's divisible by
+// both, and otherwise the number itself. The check for both has to come first, because only the
+// first true condition in an `if`/`else if` chain runs.
+#[allow(dead_code)]
+fn fizzbuzz(n: u32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for number in 1..=n {
+        let line = if number % 15 == 0 {
+            String::from("FizzBuzz")
+        } else if number % 3 == 0 {
+            String::from("Fizz")
+        } else if number % 5 == 0 {
+            String::from("Buzz")
+        } else {
+            number.to_string()
+        };
+        lines.push(line);
+    }
+
+    lines
+}
+
+// The Collatz sequence starts at `n`, then halves the number when it's even and turns it into
+// `3n + 1` when it's odd. Nobody has found a starting number that doesn't eventually reach 1, but
+// nobody has proved that they all do, either, so a `while` loop that runs until `n` is 1 is the
+// natural way to count the steps. 0 never gets there, so it's `None`. For a large enough odd `n`,
+// `3n + 1` won't fit in a `u64`; `checked_mul` and `checked_add` return `None` instead of
+// overflowing, and the `?` operator returns that `None` from the function. Evenness is checked with
+// `%` like everywhere else in this file, rather than the newer `is_multiple_of` Clippy asks for.
+#[allow(dead_code)]
+#[allow(clippy::manual_is_multiple_of)]
+fn collatz_steps(mut n: u64) -> Option<u64> {
+    if n == 0 {
+        return None;
+    }
+
+    let mut steps = 0;
+    while n != 1 {
+        n = if n % 2 == 0 {
+            n / 2
+        } else {
+            n.checked_mul(3)?.checked_add(1)?
+        };
+        steps += 1;
+    }
+
+    Some(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_until_succeeds_on_third_call() {
+        let mut calls = 0;
+        let result = retry_until(
+            || {
+                calls += 1;
+                if calls == 3 {
+                    Some("done")
+                } else {
+                    None
+                }
+            },
+            5,
+        );
+        assert_eq!(Some("done"), result);
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    fn fizzbuzz_values() {
+        let lines = fizzbuzz(15);
+        assert_eq!(15, lines.len());
+        assert_eq!("1", lines[0]);
+        assert_eq!("Fizz", lines[2]);
+        assert_eq!("Buzz", lines[4]);
+        assert_eq!("FizzBuzz", lines[14]);
+        assert!(fizzbuzz(0).is_empty());
+    }
+
+    #[test]
+    fn collatz_steps_to_one() {
+        assert_eq!(Some(0), collatz_steps(1));
+        // 6, 3, 10, 5, 16, 8, 4, 2, 1
+        assert_eq!(Some(8), collatz_steps(6));
+        assert_eq!(Some(111), collatz_steps(27));
+    }
+
+    #[test]
+    fn collatz_steps_without_an_answer() {
+        assert_eq!(None, collatz_steps(0));
+        assert_eq!(None, collatz_steps(u64::MAX));
+    }
+
+    #[test]
+    fn retry_until_gives_up() {
+        let mut calls = 0;
+        let result: Option<u32> = retry_until(
+            || {
+                calls += 1;
+                None
+            },
+            4,
+        );
+        assert_eq!(None, result);
+        assert_eq!(4, calls);
+    }
+}
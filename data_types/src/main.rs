@@ -6,6 +6,7 @@ fn main() {
     char_type();
     tuple_type();
     accessing_array_elements();
+    describe_sizes();
     invalid_array_element_access();
 }
 
@@ -116,6 +117,9 @@ fn accessing_array_elements() {
 // In many low-level languages, this kind of check is not done, and when you provide an incorrect
 // index, invalid memory can be accessed. Rust protects you against this kind of error by immediately
 // exiting instead of allowing the memory access and continuing.
+// Newer compilers can spot a constant out-of-bounds index like this one and refuse to build, so the
+// `unconditional_panic` lint is allowed here to keep the panic at runtime.
+#[allow(unconditional_panic)]
 fn invalid_array_element_access() {
     let a = [1, 2, 3, 4, 5];
     let index = 10;
@@ -123,3 +127,498 @@ fn invalid_array_element_access() {
     let element = a[index];
     println!("The value of element is: {}", element);
 }
+
+// When an index might be out of bounds and that isn't a bug, we can check it ourselves instead of
+// letting the program panic. `safe_get` returns `None` for an index past the end, the same as the
+// `get` method that slices and vectors already have. `safe_get_or` hands back `default` instead;
+// `default` has to live as long as `slice` because either one could be the reference returned.
+#[allow(dead_code)]
+fn safe_get<T>(slice: &[T], index: usize) -> Option<&T> {
+    if index < slice.len() {
+        Some(&slice[index])
+    } else {
+        None
+    }
+}
+
+#[allow(dead_code)]
+fn safe_get_or<'a, T>(slice: &'a [T], index: usize, default: &'a T) -> &'a T {
+    safe_get(slice, index).unwrap_or(default)
+}
+
+// Every type above has a size that's known at compile time, and `std::mem::size_of` tells us what
+// it is in bytes. The number in an integer or float type's name is its size in bits, so an `i32`
+// takes 4 bytes; a `char` is 4 bytes too, as mentioned above. A tuple or an array takes at least as
+// much room as its elements put together, and a tuple can take more: the compiler may add padding
+// so each field lines up in memory the way the CPU wants.
+fn describe_sizes() -> Vec<(&'static str, usize)> {
+    use std::mem::size_of;
+
+    let sizes = vec![
+        ("i8", size_of::<i8>()),
+        ("i32", size_of::<i32>()),
+        ("i64", size_of::<i64>()),
+        ("f64", size_of::<f64>()),
+        ("bool", size_of::<bool>()),
+        ("char", size_of::<char>()),
+        ("(i32, f64, u8)", size_of::<(i32, f64, u8)>()),
+        ("[i32; 5]", size_of::<[i32; 5]>()),
+    ];
+    for (name, size) in &sizes {
+        println!("{} is {} bytes", name, size);
+    }
+    sizes
+}
+
+// Integers have a fixed size too, so the result of arithmetic may not fit. In a debug build an
+// overflowing `+` panics, like the out-of-bounds index above; in a release build it silently wraps
+// around. When overflow is a real possibility it's better to say what should happen, and the
+// integer types have methods for each choice:
+//
+// - `checked_add` returns `None` when the result doesn't fit
+// - `saturating_add` stops at the type's maximum (or minimum) value
+// - `wrapping_add` wraps around, so for a `u8` 255 + 1 is 0
+#[allow(dead_code)]
+fn add_u8(a: u8, b: u8) -> Option<u8> {
+    a.checked_add(b)
+}
+
+#[allow(dead_code)]
+fn add_u8_saturating(a: u8, b: u8) -> u8 {
+    a.saturating_add(b)
+}
+
+#[allow(dead_code)]
+fn add_u8_wrapping(a: u8, b: u8) -> u8 {
+    a.wrapping_add(b)
+}
+
+// Arrays are a handy way to look at the bits that make up an integer. `bits_of` returns all 32 bits
+// of a `u32`, most-significant bit first, as an array of Booleans.
+#[allow(dead_code)]
+fn bits_of(n: u32) -> [bool; 32] {
+    let mut bits = [false; 32];
+    for (i, bit) in bits.iter_mut().enumerate() {
+        *bit = (n >> (31 - i)) & 1 == 1;
+    }
+    bits
+}
+
+// The reverse of `bits_of`: rebuilds a `u32` from bits given most-significant first. If more than
+// 32 bits are passed in, the leading ones are shifted out and only the last 32 count.
+#[allow(dead_code)]
+fn from_bits(bits: &[bool]) -> u32 {
+    bits.iter().fold(0, |n, &bit| (n << 1) | bit as u32)
+}
+
+// Roman numerals fit nicely in a `u32`. Each letter has a value, and a letter written before a larger
+// one is subtracted instead of added, so "IV" is 4 and "XC" is 90. Parsing returns a `Result` so a
+// bad letter or a badly formed numeral becomes an `Err` with a message instead of a wrong number.
+//
+// Adding and subtracting alone would happily accept numerals like "IIII" or "VX", so once we have a
+// number we write it back out as a Roman numeral and check that we get the input back. That check
+// can't catch "MMMM": `to_roman` has no letter bigger than M, so it writes 4000 the same way. The
+// standard numerals stop at 3999 (MMMCMXCIX), so anything larger is rejected on its own.
+const ROMAN_NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+#[allow(dead_code)]
+fn parse_roman(s: &str) -> Result<u32, String> {
+    let numeral = s.to_uppercase();
+    if numeral.is_empty() {
+        return Err(String::from("an empty string is not a Roman numeral"));
+    }
+
+    let mut values = Vec::new();
+    for c in numeral.chars() {
+        let value = match c {
+            'I' => 1,
+            'V' => 5,
+            'X' => 10,
+            'L' => 50,
+            'C' => 100,
+            'D' => 500,
+            'M' => 1000,
+            _ => return Err(format!("'{}' is not a Roman numeral letter", c)),
+        };
+        values.push(value);
+    }
+
+    let mut total = 0;
+    for (i, &value) in values.iter().enumerate() {
+        match values.get(i + 1) {
+            Some(&next) if next > value => total -= value as i64,
+            _ => total += value as i64,
+        }
+    }
+
+    if total <= 0 || total > 3999 || to_roman(total as u32) != numeral {
+        return Err(format!("\"{}\" is not a well-formed Roman numeral", s));
+    }
+
+    Ok(total as u32)
+}
+
+fn to_roman(mut n: u32) -> String {
+    let mut numeral = String::new();
+    for &(value, letters) in ROMAN_NUMERALS.iter() {
+        while n >= value {
+            numeral.push_str(letters);
+            n -= value;
+        }
+    }
+    numeral
+}
+
+// The floating-point types are a natural fit for temperatures. An enum lets one type hold a reading
+// in any of the three common scales, and each method converts whatever we have into the scale we
+// want as a plain `f64`. Converting through Celsius keeps each formula in one place.
+#[allow(dead_code)]
+enum Temperature {
+    Celsius(f64),
+    Fahrenheit(f64),
+    Kelvin(f64),
+}
+
+#[allow(dead_code)]
+impl Temperature {
+    fn to_celsius(&self) -> f64 {
+        match *self {
+            Temperature::Celsius(degrees) => degrees,
+            Temperature::Fahrenheit(degrees) => (degrees - 32.0) * 5.0 / 9.0,
+            Temperature::Kelvin(degrees) => degrees - 273.15,
+        }
+    }
+
+    fn to_fahrenheit(&self) -> f64 {
+        self.to_celsius() * 9.0 / 5.0 + 32.0
+    }
+
+    fn to_kelvin(&self) -> f64 {
+        self.to_celsius() + 273.15
+    }
+}
+
+// Another way to keep scales apart is to give each one its own type. A tuple struct with a single
+// field, called a "newtype", wraps an `f64` so that a `Celsius` can't be passed where a `Fahrenheit`
+// is expected by mistake. Implementing the standard `From` trait for each direction gives us the
+// conversions, and Rust then provides `Into` for free, so `let f: Fahrenheit = c.into();` works too.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Celsius(f64);
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Fahrenheit(f64);
+
+impl From<Celsius> for Fahrenheit {
+    fn from(c: Celsius) -> Fahrenheit {
+        Fahrenheit(c.0 * 9.0 / 5.0 + 32.0)
+    }
+}
+
+impl From<Fahrenheit> for Celsius {
+    fn from(f: Fahrenheit) -> Celsius {
+        Celsius((f.0 - 32.0) * 5.0 / 9.0)
+    }
+}
+
+// A struct is a good way to return several related numbers at once. `stats` walks the slice a single
+// time, keeping the smallest and largest values and a running total as it goes. An empty slice has no
+// minimum or maximum, and its mean would be a division by zero, so we return `None` for it.
+#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
+struct Stats {
+    min: f64,
+    max: f64,
+    mean: f64,
+    count: usize,
+}
+
+#[allow(dead_code)]
+fn stats(values: &[f64]) -> Option<Stats> {
+    let (&first, rest) = values.split_first()?;
+    let mut min = first;
+    let mut max = first;
+    let mut sum = first;
+
+    for &value in rest {
+        min = min.min(value);
+        max = max.max(value);
+        sum += value;
+    }
+
+    Some(Stats {
+        min,
+        max,
+        mean: sum / values.len() as f64,
+        count: values.len(),
+    })
+}
+
+use std::cmp::Ordering;
+
+// Slices and `Ordering` together make a binary search. Each time around the loop we compare the
+// middle of the part of `slice` that's left against `target`, and `match` on the `Ordering` to
+// throw away the half that can't contain it. Like the standard library's `binary_search`, we return
+// `Ok` with the index if the target is found, and otherwise `Err` with the index where it could be
+// inserted to keep the slice sorted.
+#[allow(dead_code)]
+fn binary_search<T: Ord>(slice: &[T], target: &T) -> Result<usize, usize> {
+    // the target, if it's there, is somewhere in `low..high`
+    let mut low = 0;
+    let mut high = slice.len();
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match slice[mid].cmp(target) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Ok(mid),
+        }
+    }
+
+    Err(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_are_most_significant_first() {
+        let bits = bits_of(5);
+        assert!(bits[29]);
+        assert!(!bits[30]);
+        assert!(bits[31]);
+        assert_eq!(2, bits.iter().filter(|&&bit| bit).count());
+        assert!(bits_of(1 << 31)[0]);
+    }
+
+    #[test]
+    fn bits_round_trip() {
+        for &n in &[0, 1, 2, 5, 255, 1 << 31, 0xDEAD_BEEF, u32::MAX] {
+            assert_eq!(n, from_bits(&bits_of(n)));
+        }
+    }
+
+    #[test]
+    fn zero_and_max_bits() {
+        assert_eq!([false; 32], bits_of(0));
+        assert_eq!([true; 32], bits_of(u32::MAX));
+    }
+
+    #[test]
+    fn from_bits_accepts_short_slices() {
+        assert_eq!(6, from_bits(&[true, true, false]));
+        assert_eq!(0, from_bits(&[]));
+    }
+
+    #[test]
+    fn parse_roman_is_case_insensitive() {
+        assert_eq!(Ok(1994), parse_roman("MCMXciv"));
+    }
+
+    #[test]
+    fn parse_roman_subtractive_notation() {
+        assert_eq!(Ok(4), parse_roman("IV"));
+        assert_eq!(Ok(9), parse_roman("IX"));
+        assert_eq!(Ok(3999), parse_roman("MMMCMXCIX"));
+    }
+
+    #[test]
+    fn parse_roman_invalid_character() {
+        assert_eq!(
+            Err(String::from("'?' is not a Roman numeral letter")),
+            parse_roman("IIII?")
+        );
+    }
+
+    #[test]
+    fn parse_roman_malformed_sequences() {
+        assert!(parse_roman("IIII").is_err());
+        assert!(parse_roman("VX").is_err());
+        assert!(parse_roman("IIV").is_err());
+        assert!(parse_roman("").is_err());
+    }
+
+    #[test]
+    fn parse_roman_rejects_values_above_3999() {
+        assert!(parse_roman("MMMM").is_err());
+        assert!(parse_roman("MMMMCMXCIX").is_err());
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn boiling_point_of_water() {
+        let boiling = Temperature::Celsius(100.0);
+        assert_close(212.0, boiling.to_fahrenheit());
+        assert_close(373.15, boiling.to_kelvin());
+        assert_close(100.0, Temperature::Fahrenheit(212.0).to_celsius());
+        assert_close(212.0, Temperature::Kelvin(373.15).to_fahrenheit());
+    }
+
+    #[test]
+    fn freezing_point_of_water() {
+        let freezing = Temperature::Fahrenheit(32.0);
+        assert_close(0.0, freezing.to_celsius());
+        assert_close(273.15, freezing.to_kelvin());
+    }
+
+    #[test]
+    fn temperature_round_trips() {
+        for &degrees in &[-40.0, 0.0, 21.5, 100.0] {
+            let fahrenheit = Temperature::Celsius(degrees).to_fahrenheit();
+            assert_close(degrees, Temperature::Fahrenheit(fahrenheit).to_celsius());
+            let kelvin = Temperature::Celsius(degrees).to_kelvin();
+            assert_close(degrees, Temperature::Kelvin(kelvin).to_celsius());
+        }
+        assert_close(-40.0, Temperature::Celsius(-40.0).to_fahrenheit());
+    }
+
+    #[test]
+    fn celsius_into_fahrenheit() {
+        let boiling: Fahrenheit = Celsius(100.0).into();
+        assert_close(212.0, boiling.0);
+        let freezing: Fahrenheit = Celsius(0.0).into();
+        assert_close(32.0, freezing.0);
+    }
+
+    #[test]
+    fn fahrenheit_into_celsius() {
+        let boiling: Celsius = Fahrenheit(212.0).into();
+        assert_close(100.0, boiling.0);
+        assert_close(-40.0, Celsius::from(Fahrenheit(-40.0)).0);
+    }
+
+    #[test]
+    fn newtype_round_trips_stay_stable() {
+        let mut celsius = Celsius(21.5);
+        for _ in 0..10 {
+            let fahrenheit: Fahrenheit = celsius.into();
+            celsius = fahrenheit.into();
+        }
+        assert_close(21.5, celsius.0);
+    }
+
+    #[test]
+    fn stats_of_a_typical_slice() {
+        assert_eq!(
+            Some(Stats {
+                min: -1.5,
+                max: 8.0,
+                mean: 3.0,
+                count: 4,
+            }),
+            stats(&[2.0, 8.0, -1.5, 3.5])
+        );
+    }
+
+    #[test]
+    fn stats_of_a_single_value() {
+        assert_eq!(
+            Some(Stats {
+                min: 4.25,
+                max: 4.25,
+                mean: 4.25,
+                count: 1,
+            }),
+            stats(&[4.25])
+        );
+    }
+
+    #[test]
+    fn stats_of_an_empty_slice() {
+        assert_eq!(None, stats(&[]));
+    }
+
+    #[test]
+    fn binary_search_finds_present_element() {
+        let numbers = [1, 3, 5, 7, 9, 11];
+        assert_eq!(Ok(0), binary_search(&numbers, &1));
+        assert_eq!(Ok(3), binary_search(&numbers, &7));
+        assert_eq!(Ok(5), binary_search(&numbers, &11));
+    }
+
+    #[test]
+    fn binary_search_absent_element_gives_insertion_point() {
+        let numbers = [1, 3, 5, 7, 9, 11];
+        assert_eq!(Err(0), binary_search(&numbers, &0));
+        assert_eq!(Err(3), binary_search(&numbers, &6));
+        assert_eq!(Err(6), binary_search(&numbers, &12));
+    }
+
+    #[test]
+    fn binary_search_empty_slice() {
+        let empty: [i32; 0] = [];
+        assert_eq!(Err(0), binary_search(&empty, &5));
+    }
+
+    #[test]
+    fn binary_search_strings() {
+        let words = [
+            String::from("apple"),
+            String::from("banana"),
+            String::from("cherry"),
+        ];
+        assert_eq!(Ok(1), binary_search(&words, &String::from("banana")));
+        assert_eq!(Err(2), binary_search(&words, &String::from("blueberry")));
+    }
+
+    #[test]
+    fn sizes_of_scalar_types() {
+        let sizes = describe_sizes();
+        let size = |name| sizes.iter().find(|&&(n, _)| n == name).unwrap().1;
+        assert_eq!(4, size("char"));
+        assert_eq!(4, size("i32"));
+        assert_eq!(8, size("f64"));
+        assert_eq!(20, size("[i32; 5]"));
+        assert_eq!(8, sizes.len());
+    }
+
+    #[test]
+    fn add_u8_at_the_boundary() {
+        assert_eq!(Some(255), add_u8(254, 1));
+        assert_eq!(None, add_u8(255, 1));
+        assert_eq!(255, add_u8_saturating(250, 10));
+        assert_eq!(4, add_u8_wrapping(250, 10));
+        assert_eq!(0, add_u8_wrapping(255, 1));
+    }
+
+    #[test]
+    fn safe_get_in_and_out_of_bounds() {
+        let a = [1, 2, 3, 4, 5];
+        assert_eq!(Some(&1), safe_get(&a, 0));
+        assert_eq!(Some(&5), safe_get(&a, 4));
+        assert_eq!(None, safe_get(&a, 5));
+        assert_eq!(None, safe_get(&a, 10));
+    }
+
+    #[test]
+    fn safe_get_or_returns_the_default() {
+        let a = [1, 2, 3, 4, 5];
+        assert_eq!(&3, safe_get_or(&a, 2, &0));
+        assert_eq!(&0, safe_get_or(&a, 10, &0));
+    }
+}
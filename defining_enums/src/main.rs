@@ -48,6 +48,11 @@ fn main() {
 
     let home_with_enum2 = IpAddrEnum2::V4(127, 0, 0, 2);
     let loopback_with_enum2 = IpAddrEnum2::V6(String::from("::1"));
+    let parsed_home = IpAddrEnum2::parse("127.0.0.2");
+    println!(
+        "home is {}, loopback is {}, parsed home is {:?}",
+        home_with_enum2, loopback_with_enum2, parsed_home
+    );
 }
 
 // The reason enums are useful is that now both values `IpAddrKind::V4` and `IpAddrKind::V6` are of
@@ -78,11 +83,53 @@ enum IpAddrEnum {
 // 4 numeric components that will have values between 0 and 255. If we wanted to store `V4`
 // addresses a 4 u8 values but still express `V6` as one `String` value, we wouldn't be able to
 // a struct. Enums handle this with ease.
+#[derive(Debug, PartialEq)]
 enum IpAddrEnum2 {
     V4(u8, u8, u8, u8),
     V6(String),
 }
 
+// Just like structs, enums can have associated functions. `parse` builds an `IpAddrEnum2` from text:
+// anything that looks like a dotted-quad ("127.0.0.1") must have exactly four parts that each fit in
+// a u8, so an octet such as 256 is an error. Anything else is stored as a `V6` address as it is.
+impl IpAddrEnum2 {
+    fn parse(s: &str) -> Result<IpAddrEnum2, String> {
+        if s.is_empty() {
+            return Err(String::from("an IP address can't be empty"));
+        }
+        if s.contains(':') || !s.contains('.') {
+            return Ok(IpAddrEnum2::V6(s.to_string()));
+        }
+
+        let mut octets = [0u8; 4];
+        let parts: Vec<&str> = s.split('.').collect();
+        if parts.len() != octets.len() {
+            return Err(format!("{} does not have four octets", s));
+        }
+        for (octet, part) in octets.iter_mut().zip(parts) {
+            *octet = part
+                .parse()
+                .map_err(|_| format!("{} is not an octet between 0 and 255", part))?;
+        }
+
+        Ok(IpAddrEnum2::V4(octets[0], octets[1], octets[2], octets[3]))
+    }
+}
+
+// Going the other way, implementing the `Display` trait lets us print an `IpAddrEnum2` with `{}` and
+// call `to_string` on it. A `V4` address is written back out as a dotted-quad, so printing what
+// `parse` returns gives us the text we started with.
+use std::fmt;
+
+impl fmt::Display for IpAddrEnum2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpAddrEnum2::V4(a, b, c, d) => write!(f, "{}.{}.{}.{}", a, b, c, d),
+            IpAddrEnum2::V6(address) => write!(f, "{}", address),
+        }
+    }
+}
+
 // Another way to store the IP address data, this way uses the exact enum and variants that we've
 // defined earlier but instead embeds the the address data inside the variants in the form of 2
 // different structs, which are differently for each variant
@@ -99,3 +146,54 @@ enum IpAddrEnum3 {
     V4(Ipv4Addr),
     V6(Ipv6Addr),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_v4() {
+        assert_eq!(
+            Ok(IpAddrEnum2::V4(127, 0, 0, 1)),
+            IpAddrEnum2::parse("127.0.0.1")
+        );
+    }
+
+    #[test]
+    fn parse_v4_octet_out_of_range() {
+        assert_eq!(
+            Err(String::from("256 is not an octet between 0 and 255")),
+            IpAddrEnum2::parse("192.168.256.1")
+        );
+    }
+
+    #[test]
+    fn parse_v4_wrong_number_of_octets() {
+        assert!(IpAddrEnum2::parse("10.0.1").is_err());
+        assert!(IpAddrEnum2::parse("10.0.0.1.5").is_err());
+    }
+
+    #[test]
+    fn parse_v6() {
+        assert_eq!(
+            Ok(IpAddrEnum2::V6(String::from("::1"))),
+            IpAddrEnum2::parse("::1")
+        );
+    }
+
+    #[test]
+    fn display_v4() {
+        assert_eq!("127.0.0.1", IpAddrEnum2::V4(127, 0, 0, 1).to_string());
+    }
+
+    #[test]
+    fn display_v6() {
+        assert_eq!("::1", IpAddrEnum2::V6(String::from("::1")).to_string());
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let address = "192.168.0.255";
+        assert_eq!(address, IpAddrEnum2::parse(address).unwrap().to_string());
+    }
+}
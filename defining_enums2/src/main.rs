@@ -39,6 +39,55 @@ impl Message {
     }
 }
 
+// Traits from the standard library can be implemented on enums too. Implementing `Display` decides
+// what `println!("{}", msg)` and `msg.to_string()` produce, with a `match` writing each variant
+// differently. The color is written as a hex code like "#ff8000": `{:02x}` prints a number in
+// lowercase hex, padded with a leading zero to two digits. Each part of a color is meant to be
+// between 0 and 255, so anything outside that range is clamped into it first.
+use std::fmt;
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Quite => write!(f, "Quit"),
+            Message::Move { x, y } => write!(f, "Move to ({}, {})", x, y),
+            Message::Write(text) => write!(f, "Write: {}", text),
+            Message::ChangeColor(r, g, b) => write!(
+                f,
+                "Color #{:02x}{:02x}{:02x}",
+                (*r).clamp(0, 255),
+                (*g).clamp(0, 255),
+                (*b).clamp(0, 255)
+            ),
+        }
+    }
+}
+
+// To give the messages some meaning, here is a small piece of state they can act on: a cursor with a
+// position, a color, and a log of what it has been told to do. `apply` uses a `match` to handle each
+// variant, and because the `Message` is borrowed, the arms bind references to the data inside it
+#[derive(Debug, Default)]
+struct Cursor {
+    x: i32,
+    y: i32,
+    color: (i32, i32, i32),
+    log: Vec<String>,
+}
+
+impl Cursor {
+    fn apply(&mut self, msg: &Message) {
+        match msg {
+            Message::Quite => self.log.push(String::from("quit")),
+            Message::Move { x, y } => {
+                self.x = *x;
+                self.y = *y;
+            }
+            Message::Write(text) => self.log.push(text.clone()),
+            Message::ChangeColor(r, g, b) => self.color = (*r, *g, *b),
+        }
+    }
+}
+
 fn main() {
     // The body of the method implemented on the `Message` enum would use `self` to get the value
     // that we called the method on. Below we've created a variable `m` that has the value
@@ -46,4 +95,59 @@ fn main() {
     // `call` method when `m.call()` runs
     let m = Message::Write(String::from("hello"));
     m.call();
+
+    let mut cursor = Cursor::default();
+    cursor.apply(&m);
+    cursor.apply(&Message::Move { x: 3, y: 4 });
+    println!("Cursor after applying messages: {:?}", cursor);
+    println!("{}", Message::ChangeColor(255, 128, 0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_a_sequence_of_messages() {
+        let messages = vec![
+            Message::Move { x: 10, y: -2 },
+            Message::Write(String::from("hello")),
+            Message::ChangeColor(255, 128, 0),
+            Message::Move { x: 1, y: 2 },
+            Message::Write(String::from("world")),
+            Message::Quite,
+        ];
+
+        let mut cursor = Cursor::default();
+        for msg in &messages {
+            cursor.apply(msg);
+        }
+
+        assert_eq!((1, 2), (cursor.x, cursor.y));
+        assert_eq!((255, 128, 0), cursor.color);
+        assert_eq!(vec!["hello", "world", "quit"], cursor.log);
+    }
+
+    #[test]
+    fn display_each_variant() {
+        assert_eq!("Quit", Message::Quite.to_string());
+        assert_eq!("Move to (3, -4)", Message::Move { x: 3, y: -4 }.to_string());
+        assert_eq!(
+            "Write: hello",
+            Message::Write(String::from("hello")).to_string()
+        );
+        assert_eq!(
+            "Color #ff8000",
+            Message::ChangeColor(255, 128, 0).to_string()
+        );
+    }
+
+    #[test]
+    fn display_color_pads_and_clamps() {
+        assert_eq!("Color #000a0f", Message::ChangeColor(0, 10, 15).to_string());
+        assert_eq!(
+            "Color #00ff00",
+            Message::ChangeColor(-20, 300, 0).to_string()
+        );
+    }
 }
@@ -29,6 +29,14 @@ fn main() {
         field_init_shorthand(String::from("some@email.com"), String::from("someusername"))
     );
     struct_update_syntax();
+    println!(
+        "Built User struct with a builder: {:?}",
+        UserBuilder::new()
+            .email("some@email.com")
+            .username("someusername")
+            .sign_in_count(3)
+            .build()
+    );
 }
 
 fn instanciate_struct_use_value() {
@@ -106,6 +114,66 @@ fn struct_update_syntax() {
     );
 }
 
+// `build_user` always creates an active user that has signed in once. When we want to choose only
+// some of the field values and fall back to those defaults for the rest, we can use a second struct
+// as a "builder". Each setter takes the builder by value and hands it back, so the calls can be
+// chained, and `build` checks that the fields without a sensible default were filled in before it
+// creates the `User`.
+struct UserBuilder {
+    username: String,
+    email: String,
+    sign_in_count: u64,
+    active: bool,
+}
+
+impl UserBuilder {
+    fn new() -> UserBuilder {
+        UserBuilder {
+            username: String::new(),
+            email: String::new(),
+            sign_in_count: 1,
+            active: true,
+        }
+    }
+
+    fn username(mut self, username: &str) -> UserBuilder {
+        self.username = username.to_string();
+        self
+    }
+
+    fn email(mut self, email: &str) -> UserBuilder {
+        self.email = email.to_string();
+        self
+    }
+
+    #[allow(dead_code)]
+    fn active(mut self, active: bool) -> UserBuilder {
+        self.active = active;
+        self
+    }
+
+    fn sign_in_count(mut self, sign_in_count: u64) -> UserBuilder {
+        self.sign_in_count = sign_in_count;
+        self
+    }
+
+    fn build(self) -> Result<User, String> {
+        if self.username.is_empty() {
+            return Err(String::from("a username is required"));
+        }
+        if self.email.is_empty() {
+            return Err(String::from("an email is required"));
+        }
+
+        Ok(User {
+            username: self.username,
+            email: self.email,
+            sign_in_count: self.sign_in_count,
+            active: self.active,
+        })
+    }
+}
+
 // You can also define structs that look similar to tuples called "tuple structs". Tuple strucks
 // have the added meaning the struct name provides by don't have names associated with their fields;
 // ranter, they just have the types of the fields. Tuple structs are useful when you want to give
@@ -151,3 +219,48 @@ struct SomeUnitStruct;
 // It's possible for structs to store references to data owned by something else, but to do so
 // requires the use of "lifetimes". Lifetimes ensure that the data referenced by a struct is valid
 // for as long as the struct is.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_a_user_with_the_default_fields() {
+        let user = UserBuilder::new()
+            .username("username123")
+            .email("someone@gmail.com")
+            .build()
+            .unwrap();
+
+        assert_eq!("username123", user.username);
+        assert_eq!("someone@gmail.com", user.email);
+        assert!(user.active);
+        assert_eq!(1, user.sign_in_count);
+    }
+
+    #[test]
+    fn builds_a_user_with_custom_active_and_sign_in_count() {
+        let user = UserBuilder::new()
+            .username("username123")
+            .email("someone@gmail.com")
+            .active(false)
+            .sign_in_count(42)
+            .build()
+            .unwrap();
+
+        assert!(!user.active);
+        assert_eq!(42, user.sign_in_count);
+    }
+
+    #[test]
+    fn building_without_a_username_is_an_error() {
+        let result = UserBuilder::new().email("someone@gmail.com").build();
+        assert_eq!("a username is required", result.unwrap_err());
+    }
+
+    #[test]
+    fn building_without_an_email_is_an_error() {
+        let result = UserBuilder::new().username("username123").build();
+        assert_eq!("an email is required", result.unwrap_err());
+    }
+}
@@ -291,6 +291,138 @@ fn read_username_from_file_4() -> Result<String, io::Error> {
     fs::read_to_string("hello.txt")
 }
 
+// Defining Our Own Error Type
+//
+// All of the functions above return `io::Error`, which can't easily tell the calling code the
+// difference between "there is no username file" and "the username file is there but empty"; and
+// the empty case isn't an `io::Error` at all. When a function can fail in ways that mean different
+// things to its callers, we can define an enum with one variant per kind of failure.
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ReadUsernameError {
+    // the username file doesn't exist
+    NotFound,
+    // the file exists but has nothing in it except perhaps whitespace
+    Empty,
+    // anything else that went wrong while opening or reading the file
+    Io(io::Error),
+}
+
+// The `?` operator converts errors with the `From` trait, so implementing `From<io::Error>` lets us
+// use `?` on `File::open` and `read_to_string` inside a function that returns `ReadUsernameError`.
+// The conversion is also where we pick out the missing-file case.
+impl From<io::Error> for ReadUsernameError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound => ReadUsernameError::NotFound,
+            _ => ReadUsernameError::Io(error),
+        }
+    }
+}
+
+// `Display` is what gets shown to a user, for example by `println!("{}", error)`
+impl fmt::Display for ReadUsernameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadUsernameError::NotFound => write!(f, "the username file was not found"),
+            ReadUsernameError::Empty => write!(f, "the username file is empty"),
+            ReadUsernameError::Io(error) => {
+                write!(f, "could not read the username file: {}", error)
+            }
+        }
+    }
+}
+
+// Implementing the `Error` trait makes our type a proper error, so it can be returned from `main`
+// in a `Box<dyn Error>` like any other. `source` gives access to the underlying `io::Error`.
+impl std::error::Error for ReadUsernameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadUsernameError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// The same as `read_username_from_file_3`, except that the file to read is passed in and the caller
+// gets a `ReadUsernameError` back. Surrounding whitespace, like the newline at the end of the file,
+// isn't part of the username.
+#[allow(dead_code)]
+fn read_username(path: &str) -> Result<String, ReadUsernameError> {
+    let mut s = String::new();
+    File::open(path)?.read_to_string(&mut s)?;
+
+    let username = s.trim();
+    if username.is_empty() {
+        return Err(ReadUsernameError::Empty);
+    }
+    Ok(username.to_string())
+}
+
+// A single function can hit more than one kind of error. Reading a number from a file can fail
+// while opening or reading the file (an `io::Error`) or while parsing its contents (a
+// `ParseIntError`). With one `From` implementation per error type, `?` converts each of them into
+// our own `AppError`, so the function can return a single error type.
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+pub struct AppError(String);
+
+impl From<io::Error> for AppError {
+    fn from(error: io::Error) -> Self {
+        AppError(format!("could not read the file: {}", error))
+    }
+}
+
+impl From<ParseIntError> for AppError {
+    fn from(error: ParseIntError) -> Self {
+        AppError(format!("could not parse a number: {}", error))
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[allow(dead_code)]
+fn read_and_parse(path: &str) -> Result<i32, AppError> {
+    let contents = fs::read_to_string(path)?;
+    let number = contents.trim().parse::<i32>()?;
+    Ok(number)
+}
+
+// Retrying Operations That Might Fail
+//
+// Some errors are only temporary: a file another program is still writing, or a network that drops
+// out for a moment. Because a `Result` hands the decision back to the caller, the caller can simply
+// try again. `retry` calls `op` up to `attempts` times, sleeping for `delay` after each failure, and
+// returns the first `Ok`. If every attempt fails, the `Err` from the last one is returned. `op` is
+// always called at least once, even when `attempts` is 0, so that there is a `Result` to return.
+use std::thread;
+use std::time::Duration;
+
+#[allow(dead_code)]
+fn retry<T, E, F: FnMut() -> Result<T, E>>(
+    mut op: F,
+    attempts: usize,
+    delay: Duration,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt >= attempts => return Err(error),
+            Err(_) => {
+                thread::sleep(delay);
+                attempt += 1;
+            }
+        }
+    }
+}
+
 // The `?` Operator can be used in functions that return a result
 //
 // The `?` operator can be used in functions that have a return type of `Result`, it is defined to
@@ -327,3 +459,126 @@ fn main() -> Result<(), Box<dyn Error>> {
     let f = File::open("hello.txt")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // Writes `contents` to a file in the system's temporary directory and returns its path. The
+    // process id keeps runs of the tests from stepping on each other.
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("error_handling_{}_{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_username_ok() {
+        let path = temp_file("username_ok.txt", "ferris\n");
+        let result = read_username(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_eq!("ferris", result.unwrap());
+    }
+
+    #[test]
+    fn read_username_not_found() {
+        let path = std::env::temp_dir().join("error_handling_no_such_username_file.txt");
+        match read_username(path.to_str().unwrap()) {
+            Err(ReadUsernameError::NotFound) => (),
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_username_empty() {
+        let path = temp_file("username_empty.txt", "  \n");
+        let result = read_username(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        match result {
+            Err(ReadUsernameError::Empty) => (),
+            other => panic!("expected Empty, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_username_other_io_error() {
+        // a directory can be opened but not read like a file
+        let path = std::env::temp_dir();
+        match read_username(path.to_str().unwrap()) {
+            Err(ReadUsernameError::Io(_)) => (),
+            other => panic!("expected Io, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_username_error_messages() {
+        assert_eq!(
+            "the username file was not found",
+            ReadUsernameError::NotFound.to_string()
+        );
+        assert_eq!(
+            "the username file is empty",
+            ReadUsernameError::Empty.to_string()
+        );
+    }
+
+    #[test]
+    fn read_and_parse_ok() {
+        let path = temp_file("number_ok.txt", "42\n");
+        let result = read_and_parse(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(42, result.unwrap());
+    }
+
+    #[test]
+    fn read_and_parse_missing_file() {
+        let path = std::env::temp_dir().join("error_handling_no_such_number_file.txt");
+        let AppError(message) = read_and_parse(path.to_str().unwrap()).unwrap_err();
+        assert!(message.starts_with("could not read the file"));
+    }
+
+    #[test]
+    fn read_and_parse_not_a_number() {
+        let path = temp_file("number_bad.txt", "notanumber");
+        let result = read_and_parse(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        let AppError(message) = result.unwrap_err();
+        assert!(message.starts_with("could not parse a number"));
+    }
+
+    #[test]
+    fn retry_succeeds_on_third_try() {
+        let mut calls = 0;
+        let result = retry(
+            || {
+                calls += 1;
+                if calls < 3 {
+                    Err(format!("failure {}", calls))
+                } else {
+                    Ok("username")
+                }
+            },
+            5,
+            Duration::from_secs(0),
+        );
+        assert_eq!(Ok("username"), result);
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    fn retry_returns_the_last_error() {
+        let mut calls = 0;
+        let result: Result<(), String> = retry(
+            || {
+                calls += 1;
+                Err(format!("failure {}", calls))
+            },
+            4,
+            Duration::from_secs(0),
+        );
+        assert_eq!(Err(String::from("failure 4")), result);
+        assert_eq!(4, calls);
+    }
+}
@@ -0,0 +1,85 @@
+// We define a struct named `Guess` that has a field named `value` that holds a u32. This is where the
+// number will be stored. A u32 can't be negative, so the only check left for `new` is the range.
+//
+// This module is shared: the guessing_game crate depends on this crate and uses `Guess` to check the
+// numbers the player types in.
+pub struct Guess {
+    value: u32,
+}
+
+impl Guess {
+    // Then we implement an associated function named `new` on `Guess` that creates instances of
+    // `Guess` values, as long as they're between 1 and 100.
+    //
+    // The first version of `new` called `panic!` when `value` was out of range, which makes sense
+    // when a bad value can only come from a bug in the calling code. In the guessing game an
+    // out-of-range number is something a player is expected to type, so `new` returns a `Result`
+    // instead and the caller decides what to do about it: the game tells the player and asks again.
+    pub fn new(value: u32) -> Result<Guess, String> {
+        Guess::in_range(value, 1, 100)
+    }
+
+    // A guess between `low` and `high`, both included, for when the difficulty changes the range
+    pub fn in_range(value: u32, low: u32, high: u32) -> Result<Guess, String> {
+        if value < low || value > high {
+            return Err(format!(
+                "Please enter a number between {} and {}",
+                low, high
+            ));
+        }
+        Ok(Guess { value })
+    }
+
+    // Next, we implement a method named `value` that borrows `self`, doesn't have any other
+    // parameters, and returns a u32. This kind of method is sometimes called a "getter", because
+    // its purpose is to get some data from it fields and return it. This public method is necessary
+    // because the `value` field of the `Guess` struct is private. It's important that the `value`
+    // field be private so code using the `Guess` struct is not allow to set `value` directly: code
+    // outside the module must use the `Guess::new` function to create an instance of `Guess`,
+    // thereby ensuring there's no way for a `Guess` to have a `value` that hasn't been checked
+    // by the conditions in the `Guess::new` function
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    // A function that has a paramter or returns only numbers between 1 and 100 could then declare
+    // in its signature that takes or returns a `Guess` rather than a u32 and wouldn't need to do
+    // and additional check in its body.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_is_too_small() {
+        assert_eq!(
+            Err(String::from("Please enter a number between 1 and 100")),
+            Guess::new(0).map(|guess| guess.value())
+        );
+    }
+
+    #[test]
+    fn one_is_allowed() {
+        assert_eq!(Ok(1), Guess::new(1).map(|guess| guess.value()));
+    }
+
+    #[test]
+    fn one_hundred_is_allowed() {
+        assert_eq!(Ok(100), Guess::new(100).map(|guess| guess.value()));
+    }
+
+    #[test]
+    fn one_hundred_one_is_too_big() {
+        assert!(Guess::new(101).is_err());
+    }
+
+    #[test]
+    fn custom_range() {
+        assert!(Guess::in_range(500, 1, 500).is_ok());
+        assert_eq!(
+            Err(String::from("Please enter a number between 1 and 50")),
+            Guess::in_range(51, 1, 50).map(|guess| guess.value())
+        );
+    }
+}
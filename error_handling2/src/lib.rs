@@ -0,0 +1,3 @@
+// Besides the `main.rs` binary, this crate is a library so that other crates can use the types it
+// defines. The guessing_game crate lists it as a dependency to get `Guess`.
+pub mod guess;
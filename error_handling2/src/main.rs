@@ -110,48 +110,119 @@ fn never_fail_acceptable_unwrap_call() {
 //
 // Instead, we can make a new type and put the validations in a function to create an instance of
 // the type rather than repeating the validations everywhere. That waym it's safe for functions to
-// use the new type in their signatures and confidently use the values they receive. Below shows
-// one way to define a `Guess` type that will only create an instance of `Guess` if the `new`
-// function receives a value between 1 and 100.
-
-// First, we define a struct named `Guess` that has a field named `value` that holds an i32. This is
-// where the number will be stored
-pub struct Guess {
-    value: i32,
+// use the new type in their signatures and confidently use the values they receive. `src/guess.rs`
+// shows one way to define a `Guess` type that will only create an instance of `Guess` if the `new`
+// function receives a value between 1 and 100. It lives in a module of its own, exported from this
+// crate's library, so the guessing_game crate can use the same type.
+
+// The same pattern works for any value with a valid range. A `Percentage` is a number from 0.0 to
+// 100.0, and like `Guess` its `new` returns a `Result`, so the caller decides what to do with a bad
+// value. Floating-point numbers have one extra case to think about: `NaN` ("not a number"), which is
+// what you get from something like `0.0 / 0.0`. Every comparison with `NaN` is false, so it's
+// checked on its own before the range check to give it a clearer message.
+#[derive(Debug, PartialEq)]
+pub struct Percentage {
+    value: f64,
 }
 
-impl Guess {
-    // Then we implement an associated function named `new` on `Guess` that creates instances of
-    // `Guess` values
-    pub fn new(value: i32) -> Guess {
-        // we test `value` to make sure it's between 1 and 100.
-        if value < 1 || value > 100 {
-            // If `value` doens't pass the test, we make a `panic!` call, which will alert the
-            // programmer who is writting the calling code that we have a bug that needs a fix,
-            // because creating a `Guess` with a `value` outside this range would violate the contract
-            // that `Guess::new` is relying on
-            panic!("Guess value must be between 1 and 100, got {}", value);
+impl Percentage {
+    pub fn new(value: f64) -> Result<Percentage, String> {
+        if value.is_nan() {
+            return Err(String::from("a percentage can't be NaN"));
+        }
+        if !(0.0..=100.0).contains(&value) {
+            return Err(format!(
+                "a percentage must be between 0 and 100, got {}",
+                value
+            ));
         }
-        Guess { value }
+        Ok(Percentage { value })
     }
 
-    // Next, we implement a method named `value` that borrows `self`, doesn't have any other
-    // parameters, and returns a i32. This kind of method is sometimes called a "getter", because
-    // its purpose is to get some data from it fields and return it. This public method is necessary
-    // because the `value` field of the `Guess` struct is private. It's important that the `value`
-    // field be private so code using the `Guess` struct is not allow to set `value` directly: code
-    // outside the module must use the `Guess::new` function to create an instance of `Guess`,
-    // thereby ensuring there's no way for a `Guess` to have a `value` that hasn't been checked
-    // by the conditions in the `Guess::new` function
-    pub fn value(&self) -> i32 {
+    pub fn value(&self) -> f64 {
         self.value
     }
+}
 
-    // A function that has a paramter or returns only numbers between 1 and 100 could then declare
-    // in its signature that takes or returns a `Guess` rather than an i32 and wouldn't need to do
-    // and additional check in its body.
+// When there's only one way for a value to be invalid, `Option` is enough to say so. `EvenU32::new`
+// returns `None` for an odd number. Because the field is private, every `EvenU32` that exists has
+// passed that check, so `half` can divide by 2 without asking whether there's a remainder to lose:
+// the check made once at construction stands in for a check in every method that relies on it.
+#[derive(Debug, PartialEq)]
+pub struct EvenU32 {
+    value: u32,
+}
+
+impl EvenU32 {
+    // `n % 2 == 0` rather than `is_multiple_of`, which Clippy suggests but which needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn new(n: u32) -> Option<EvenU32> {
+        if n % 2 == 0 {
+            Some(EvenU32 { value: n })
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    pub fn half(&self) -> u32 {
+        self.value / 2
+    }
 }
 
 fn main() {
     never_fail_acceptable_unwrap_call();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentage_at_the_boundaries() {
+        assert_eq!(0.0, Percentage::new(0.0).unwrap().value());
+        assert_eq!(100.0, Percentage::new(100.0).unwrap().value());
+        assert_eq!(42.5, Percentage::new(42.5).unwrap().value());
+    }
+
+    #[test]
+    fn percentage_out_of_range() {
+        assert_eq!(
+            Err(String::from(
+                "a percentage must be between 0 and 100, got 100.5"
+            )),
+            Percentage::new(100.5)
+        );
+        assert!(Percentage::new(-0.1).is_err());
+    }
+
+    #[test]
+    fn percentage_nan() {
+        assert_eq!(
+            Err(String::from("a percentage can't be NaN")),
+            Percentage::new(f64::NAN)
+        );
+    }
+
+    #[test]
+    fn even_u32_accepts_even_numbers() {
+        assert_eq!(Some(10), EvenU32::new(10).map(|n| n.value()));
+        assert!(EvenU32::new(0).is_some());
+    }
+
+    #[test]
+    fn even_u32_rejects_odd_numbers() {
+        assert_eq!(None, EvenU32::new(7));
+        assert_eq!(None, EvenU32::new(u32::MAX));
+    }
+
+    #[test]
+    fn even_u32_half() {
+        assert_eq!(5, EvenU32::new(10).unwrap().half());
+        assert_eq!(0, EvenU32::new(0).unwrap().half());
+        assert_eq!(u32::MAX / 2, EvenU32::new(u32::MAX - 1).unwrap().half());
+    }
+}
@@ -25,8 +25,10 @@
     then return whatever number we passed in.
 */
 
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 fn simulated_expensive_calculation(intensity: u32) -> u32 {
     println!("calculating slowly...");
@@ -335,3 +337,313 @@ fn generate_workout(intensity: u32, random_number: u32) {
     closures have an additional capability that functions don't have: they can capture their
     environment and access variables from the scope in which they're defiined.
 */
+
+/*
+    Closures as Arguments: a Stopwatch
+
+    Because a closure stores code to run later, a function can take one as an argument and decide
+    what to do around the call. `time_it` runs the closure `f` once, measures how long it took with
+    `Instant`, prints the label along with the elapsed time, and hands back both the closure's result
+    and the `Duration`. The closure only needs to be called once, so `FnOnce` is the most permissive
+    bound we can use.
+*/
+#[allow(dead_code)]
+fn time_it<T>(label: &str, f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    println!("{} took {:?}", label, elapsed);
+    (result, elapsed)
+}
+
+/*
+    Closures that get called more than once and change their captured state need the `FnMut` bound.
+    `retry_timed` keeps calling `f` until it returns `Ok` or until `attempts` calls have been made,
+    timing each call with `time_it`. It returns the last result along with how many attempts were
+    actually made, which gives some visibility into how flaky an operation is. The closure is always
+    called at least once, even if `attempts` is 0, because otherwise there'd be no result to return.
+*/
+#[allow(dead_code)]
+fn retry_timed<T, E>(attempts: u32, mut f: impl FnMut() -> Result<T, E>) -> (Result<T, E>, u32) {
+    let mut made = 0;
+    loop {
+        made += 1;
+        let (result, _) = time_it(&format!("attempt {}", made), &mut f);
+        if result.is_ok() || made >= attempts {
+            return (result, made);
+        }
+    }
+}
+
+/*
+    Memoizing with a HashMap
+
+    The `Cacher` above only remembers a single value, no matter what argument it's called with. To
+    remember a result for every argument, the cache has to be a `HashMap` keyed by the argument
+    instead of an `Option`. `FibMemo` does this for Fibonacci numbers: each number is only computed
+    once, and asking for it again is a lookup. `computed` counts how many numbers have actually been
+    calculated, so we can see the cache at work.
+
+    The 93rd Fibonacci number is the largest one that fits in a u64. Rather than silently saturating
+    at `u64::MAX`, which would hand back a wrong number, `get` returns an `Err` for anything past
+    `fib(93)`. It checks this before recursing: each call asks for `n - 1` first, so a huge `n` would
+    otherwise nest a million calls deep and overflow the stack long before any addition overflowed.
+*/
+#[allow(dead_code)]
+struct FibMemo {
+    cache: HashMap<u64, u64>,
+    computed: u32,
+}
+
+#[allow(dead_code)]
+impl FibMemo {
+    fn new() -> FibMemo {
+        FibMemo {
+            cache: HashMap::new(),
+            computed: 0,
+        }
+    }
+
+    fn get(&mut self, n: u64) -> Result<u64, String> {
+        if n > 93 {
+            return Err(format!("fib({}) does not fit in a u64", n));
+        }
+        if let Some(&v) = self.cache.get(&n) {
+            return Ok(v);
+        }
+
+        let v = if n < 2 {
+            n
+        } else {
+            self.get(n - 1)? + self.get(n - 2)?
+        };
+        self.computed += 1;
+        self.cache.insert(n, v);
+        Ok(v)
+    }
+}
+
+/*
+    Sometimes we already own a `HashMap` and don't want to wrap it in a struct like `Cacher` just to
+    cache values in it. `cache_or_compute` takes the map, a key, and a closure that can produce the
+    value. The `entry` API looks the key up once: if there's already a value we get a reference to
+    it, and only if the key is missing does `or_insert_with` call the closure and store its result.
+    Since the closure is called at most once, `FnOnce` is all we need. The returned reference
+    borrows from the map; because the map is the only reference we take, the lifetime elision rules
+    connect the two for us and we don't need to write a lifetime parameter.
+*/
+#[allow(dead_code)]
+fn cache_or_compute<K: Eq + Hash, V, F: FnOnce() -> V>(
+    cache: &mut HashMap<K, V>,
+    key: K,
+    compute: F,
+) -> &V {
+    cache.entry(key).or_insert_with(compute)
+}
+
+/*
+    A Bounded Cache
+
+    `FibMemo` and `cache_or_compute` keep every value they're given, so the cache can grow forever.
+    `LruCache` holds at most `capacity` values. Along with the `HashMap` of values, it keeps a
+    `VecDeque` of the keys in the order they were last used, oldest at the front. Both `get` and
+    `put` move the key they touch to the back; when `put` needs to make room, the key at the front is
+    the "least recently used" one, and that's the value we throw away.
+
+    Moving a key to the back means finding it in the `VecDeque` first, which takes time proportional
+    to the number of keys. That's fine for the small caches we're using here; a cache holding many
+    thousands of values would want a linked list that the map can point into instead.
+*/
+#[allow(dead_code)]
+struct LruCache<K, V> {
+    capacity: usize,
+    values: HashMap<K, V>,
+    order: VecDeque<K>,
+}
+
+#[allow(dead_code)]
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    fn new(capacity: usize) -> LruCache<K, V> {
+        LruCache {
+            capacity,
+            values: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if self.values.contains_key(key) {
+            self.touch(key);
+        }
+        self.values.get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.values.contains_key(&key) {
+            self.touch(&key);
+        } else {
+            if self.values.len() == self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.values.remove(&oldest);
+                }
+            }
+            self.order.push_back(key.clone());
+        }
+        self.values.insert(key, value);
+    }
+
+    // Marks `key` as the most recently used by moving it to the back of `order`
+    fn touch(&mut self, key: &K) {
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(position) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn time_it_returns_the_closure_result() {
+        let (result, elapsed) = time_it("sum", || (1..=10).sum::<u32>());
+        assert_eq!(55, result);
+        assert!(elapsed >= Duration::from_secs(0));
+    }
+
+    #[test]
+    fn time_it_measures_the_closure() {
+        let (_, elapsed) = time_it("sleep", || thread::sleep(Duration::from_millis(5)));
+        assert!(elapsed >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn retry_timed_success_on_first_try() {
+        let (result, attempts) = retry_timed(5, || Ok::<_, String>(42));
+        assert_eq!(Ok(42), result);
+        assert_eq!(1, attempts);
+    }
+
+    #[test]
+    fn retry_timed_success_after_two_failures() {
+        let mut calls = 0;
+        let (result, attempts) = retry_timed(5, || {
+            calls += 1;
+            if calls < 3 {
+                Err("not yet")
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(Ok(3), result);
+        assert_eq!(3, attempts);
+    }
+
+    #[test]
+    fn retry_timed_gives_up_after_the_last_attempt() {
+        let (result, attempts) = retry_timed(4, || Err::<(), _>("always"));
+        assert_eq!(Err("always"), result);
+        assert_eq!(4, attempts);
+    }
+
+    #[test]
+    fn fib_memo_known_values() {
+        let mut fib = FibMemo::new();
+        assert_eq!(Ok(0), fib.get(0));
+        assert_eq!(Ok(1), fib.get(1));
+        assert_eq!(Ok(55), fib.get(10));
+        assert_eq!(Ok(12_200_160_415_121_876_738), fib.get(93));
+    }
+
+    #[test]
+    fn fib_memo_does_not_recompute() {
+        let mut fib = FibMemo::new();
+        assert_eq!(Ok(55), fib.get(10));
+        let computed = fib.computed;
+        assert_eq!(11, computed);
+
+        assert_eq!(Ok(55), fib.get(10));
+        assert_eq!(Ok(21), fib.get(8));
+        assert_eq!(computed, fib.computed);
+    }
+
+    #[test]
+    fn fib_memo_overflow_is_an_error() {
+        let mut fib = FibMemo::new();
+        assert!(fib.get(94).is_err());
+        assert!(fib.get(93).is_ok());
+    }
+
+    #[test]
+    fn fib_memo_very_large_n_is_an_error_not_a_stack_overflow() {
+        let mut fib = FibMemo::new();
+        assert!(fib.get(1_000_000).is_err());
+        assert_eq!(0, fib.computed);
+    }
+
+    #[test]
+    fn cache_or_compute_only_computes_once_per_key() {
+        let calls = Cell::new(0);
+        let compute = |value: u32| {
+            calls.set(calls.get() + 1);
+            value * 10
+        };
+        let mut cache = HashMap::new();
+
+        assert_eq!(&10, cache_or_compute(&mut cache, "one", || compute(1)));
+        assert_eq!(1, calls.get());
+
+        // the closure isn't called again for a key that is already cached
+        assert_eq!(&10, cache_or_compute(&mut cache, "one", || compute(100)));
+        assert_eq!(1, calls.get());
+
+        assert_eq!(&20, cache_or_compute(&mut cache, "two", || compute(2)));
+        assert_eq!(2, calls.get());
+    }
+
+    #[test]
+    fn lru_cache_evicts_the_oldest_entry() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put("c", 3);
+
+        assert_eq!(None, cache.get(&"a"));
+        assert_eq!(Some(&2), cache.get(&"b"));
+        assert_eq!(Some(&3), cache.get(&"c"));
+    }
+
+    #[test]
+    fn lru_cache_get_refreshes_recency() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        // using "a" makes "b" the least recently used
+        assert_eq!(Some(&1), cache.get(&"a"));
+        cache.put("c", 3);
+
+        assert_eq!(None, cache.get(&"b"));
+        assert_eq!(Some(&1), cache.get(&"a"));
+        assert_eq!(Some(&3), cache.get(&"c"));
+    }
+
+    #[test]
+    fn lru_cache_put_existing_key_updates_without_evicting() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put("a", 10);
+        cache.put("c", 3);
+
+        assert_eq!(Some(&10), cache.get(&"a"));
+        assert_eq!(None, cache.get(&"b"));
+        assert_eq!(Some(&3), cache.get(&"c"));
+    }
+}
@@ -86,6 +86,7 @@ fn largest_char(list: &[char]) -> &char {
 // First, we declare the name of the type parameter inside angle brackets just after the name of the
 // struct
 #[allow(dead_code)]
+#[derive(Debug, PartialEq)]
 struct Point<T> {
     // Then we can use the generic type in the struct definition where wouold otherwise specify
     // concrete data types
@@ -170,6 +171,45 @@ impl Point<f32> {
     fn distance_from_origin(&self) -> f32 {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
+
+    // The straight-line (Euclidean) distance between this point and `other`
+    #[allow(dead_code)]
+    fn distance_to(&self, other: &Point<f32>) -> f32 {
+        euclidean_distance(f64::from(self.x - other.x), f64::from(self.y - other.y)) as f32
+    }
+}
+
+// A second block specialized for `Point<f64>` gives those points a `distance_to` method too. Both
+// methods hand the differences between the coordinates to `euclidean_distance`, so the math is only
+// written once: an `f32` converts to an `f64` without losing anything, and the `f32` method converts
+// the result back at the end.
+impl Point<f64> {
+    #[allow(dead_code)]
+    fn distance_to(&self, other: &Point<f64>) -> f64 {
+        euclidean_distance(self.x - other.x, self.y - other.y)
+    }
+}
+
+fn euclidean_distance(dx: f64, dy: f64) -> f64 {
+    (dx.powi(2) + dy.powi(2)).sqrt()
+}
+
+// Trait bounds work in `impl` blocks too. Implementing the `std::ops::Add` trait is how Rust lets us
+// overload the `+` operator (the strings crate shows `String` doing the same thing). Here we only
+// implement it for `Point<T>` when `T` itself can be added to a `T` to produce a `T`, so `p1 + p2`
+// works for `Point<i32>`, `Point<f64>`, or a point of any of our own types that implement `Add`,
+// and adds the points component by component.
+use std::ops::Add;
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
 }
 
 // Generic type parameters in a struct definition aren't alway the same as those you use in that
@@ -193,6 +233,31 @@ impl<T, U> PointMultipleTypeParameters<T, U> {
     }
 }
 
+// Methods can add trait bounds of their own on top of the ones on the `impl` block. `try_mixup` does
+// the same mixing as `mixup`, but only for points whose kept values implement `Display`, so it can
+// check them before building the new point: if either value would display as an empty string, we
+// get an `Err` explaining which one instead of a point. Any `V` is fine because `other.x` is thrown
+// away. Note that we have to write `std::result::Result` here, because this file defines its own
+// `Result` enum above, which would otherwise be the one we get.
+use std::fmt::Display;
+
+impl<T: Display, U> PointMultipleTypeParameters<T, U> {
+    #[allow(dead_code)]
+    fn try_mixup<V, W: Display>(
+        self,
+        other: PointMultipleTypeParameters<V, W>,
+    ) -> std::result::Result<PointMultipleTypeParameters<T, W>, String> {
+        if self.x.to_string().is_empty() {
+            return Err(String::from("the x value displays as an empty string"));
+        }
+        if other.y.to_string().is_empty() {
+            return Err(String::from("the y value displays as an empty string"));
+        }
+
+        Ok(self.mixup(other))
+    }
+}
+
 fn main() {
     let number_list = vec![34, 50, 25, 100, 65];
 
@@ -209,6 +274,9 @@ fn main() {
 
     let p2: Point<f32> = Point { x: 5.0, y: 10.0 };
     println!("Distance from Origin is: {}", p2.distance_from_origin());
+
+    let p6 = Point { x: 1, y: 2 } + Point { x: 3, y: 4 };
+    println!("p6.x = {}, p6.y = {}", p6.x, p6.y);
     // Here we've defined a `PointMutlipleTypeParameters` that has an i32 for `x` (with value of 5)
     // and an f64 for `y` (with a value of 10.4). The `p4` variable has a string slice for `x` (with
     // a value of "Hello") and a char for `y` (with a value of 'c').
@@ -230,3 +298,105 @@ fn main() {
 // Rust accomplishes this by performing monomorphization of the code that is using generics at compile
 // time. "Monomorphization" is the process of turning generic code into specific code by filling in
 // the concrete types that used when compiled.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_to_f32() {
+        let origin: Point<f32> = Point { x: 0.0, y: 0.0 };
+        let p: Point<f32> = Point { x: 3.0, y: 4.0 };
+        assert_eq!(5.0, origin.distance_to(&p));
+        assert_eq!(5.0, p.distance_to(&origin));
+    }
+
+    #[test]
+    fn distance_to_f64() {
+        let origin: Point<f64> = Point { x: 0.0, y: 0.0 };
+        let p: Point<f64> = Point { x: 3.0, y: 4.0 };
+        assert_eq!(5.0, origin.distance_to(&p));
+    }
+
+    #[test]
+    fn distance_to_itself_is_zero() {
+        let p32: Point<f32> = Point { x: -1.5, y: 2.25 };
+        let p64: Point<f64> = Point { x: -1.5, y: 2.25 };
+        assert_eq!(0.0, p32.distance_to(&p32));
+        assert_eq!(0.0, p64.distance_to(&p64));
+    }
+
+    #[test]
+    fn add_i32_points() {
+        let sum = Point { x: 1, y: 2 } + Point { x: 10, y: -20 };
+        assert_eq!(Point { x: 11, y: -18 }, sum);
+    }
+
+    #[test]
+    fn add_f64_points() {
+        let sum = Point { x: 1.5, y: 0.25 } + Point { x: 2.0, y: 0.5 };
+        assert_eq!(3.5, sum.x);
+        assert_eq!(0.75, sum.y);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Meters(u32);
+
+    impl Add for Meters {
+        type Output = Meters;
+
+        fn add(self, other: Meters) -> Meters {
+            Meters(self.0 + other.0)
+        }
+    }
+
+    #[test]
+    fn add_points_of_custom_numeric_type() {
+        let sum = Point {
+            x: Meters(1),
+            y: Meters(2),
+        } + Point {
+            x: Meters(3),
+            y: Meters(4),
+        };
+        assert_eq!(
+            Point {
+                x: Meters(4),
+                y: Meters(6)
+            },
+            sum
+        );
+    }
+
+    #[test]
+    fn try_mixup_success() {
+        let p1 = PointMultipleTypeParameters { x: 5, y: 10.4 };
+        let p2 = PointMultipleTypeParameters { x: "Hello", y: 'c' };
+        let p3 = p1.try_mixup(p2).unwrap();
+        assert_eq!(5, p3.x);
+        assert_eq!('c', p3.y);
+    }
+
+    #[test]
+    fn try_mixup_empty_x() {
+        let p1 = PointMultipleTypeParameters { x: "", y: 1 };
+        let p2 = PointMultipleTypeParameters { x: 2, y: "world" };
+        assert_eq!(
+            "the x value displays as an empty string",
+            p1.try_mixup(p2).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn try_mixup_empty_y() {
+        let p1 = PointMultipleTypeParameters { x: "hello", y: 1 };
+        let p2 = PointMultipleTypeParameters {
+            x: 2,
+            y: String::new(),
+        };
+        assert_eq!(
+            "the y value displays as an empty string",
+            p1.try_mixup(p2).err().unwrap()
+        );
+    }
+}
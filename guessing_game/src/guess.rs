@@ -0,0 +1,68 @@
+use std::fmt;
+
+// `Guess` is defined in the error_handling2 crate, which is listed under `[dependencies]` in our
+// Cargo.toml with a `path` instead of a version, since it lives in this repository too
+use error_handling2::guess::Guess;
+
+// What can be wrong with the line the player typed in as a guess
+#[derive(Debug, PartialEq)]
+pub enum GuessError {
+    NotANumber,
+    OutOfRange { got: u32, lo: u32, hi: u32 },
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::NotANumber => write!(f, "Please type a number!"),
+            GuessError::OutOfRange { got, lo, hi } => write!(
+                f,
+                "{} is out of range. Please enter a number between {} and {}",
+                got, lo, hi
+            ),
+        }
+    }
+}
+
+// Turns a line of input into a guess between `lo` and `hi`. The input is trimmed first, so the
+// newline from pressing enter doesn't stop it from parsing, and the range is checked by
+// `Guess::in_range`.
+pub fn parse_guess(input: &str, lo: u32, hi: u32) -> Result<u32, GuessError> {
+    let got: u32 = input.trim().parse().map_err(|_| GuessError::NotANumber)?;
+
+    match Guess::in_range(got, lo, hi) {
+        Ok(guess) => Ok(guess.value()),
+        Err(_) => Err(GuessError::OutOfRange { got, lo, hi }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_a_valid_guess() {
+        assert_eq!(Ok(42), parse_guess("  42\n", 1, 100));
+    }
+
+    #[test]
+    fn parse_non_numeric_input() {
+        assert_eq!(
+            Err(GuessError::NotANumber),
+            parse_guess("forty two\n", 1, 100)
+        );
+        assert_eq!(Err(GuessError::NotANumber), parse_guess("-5", 1, 100));
+    }
+
+    #[test]
+    fn parse_out_of_range_number() {
+        assert_eq!(
+            Err(GuessError::OutOfRange {
+                got: 101,
+                lo: 1,
+                hi: 100
+            }),
+            parse_guess("101\n", 1, 100)
+        );
+    }
+}
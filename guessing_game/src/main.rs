@@ -7,19 +7,48 @@ use rand::Rng;
 // Bring `Ordering` enum into scope
 use std::cmp::Ordering;
 
+// Reading a guess from the player's input lives in its own module, in `src/guess.rs`
+mod guess;
+use guess::parse_guess;
+
+// how many guesses the player gets before losing
+const MAX_ATTEMPTS: u32 = 7;
+
 fn main() {
     println!("Guess the number!");
 
+    println!("Choose a difficulty: easy (1-50), medium (1-100) or hard (1-500).");
+
+    let mut difficulty = String::new();
+
+    io::stdin()
+        .read_line(&mut difficulty)
+        .expect("Failed to read line");
+
+    // the range the secret number is picked from, and the only guesses we'll accept
+    let (low, high) = parse_difficulty(&difficulty);
+
     // the `rand::thread_rng` function will give us the random number generator that we want to use
     // the `gen_range` method takes two numbers as arguments and generates a random number in
     // between
     // NOTE: You won't just know which traits to use and which methods and functions to call from
     // a crate. Instructions for using a crate are in each crate's documentation. You can run
     // `cargo doc --open` to read documentation
-    let secret_number = rand::thread_rng().gen_range(1, 101);
+    //
+    // Picking the number is done by `pick_secret`, which we hand the generator to use. Here that's
+    // the thread's random number generator, while the tests can pass a seeded one and know exactly
+    // which number they'll get
+    let secret_number = pick_secret(&mut rand::thread_rng(), low, high);
+
+    // only guesses that make it past the checks below count as an attempt
+    let mut guesses_made = 0;
 
     // the `loop` keyword creats an infinite loop.
     loop {
+        println!(
+            "Attempts remaining: {}",
+            attempts_remaining(MAX_ATTEMPTS, guesses_made)
+        );
         println!("Please input your guess.");
 
         let mut guess = String::new();
@@ -67,23 +96,22 @@ fn main() {
         //
         // If `parse` is not able to create a number from the string it will return the `Err` value
         // match the second arm of our `match` expression.
-        // The `_` (underscore) is a catchall value, here we are saying want to match all Errors
-        // The program will not crash but will instead just move on to the next iteration of the
-        // loop, so basically just ignoring the incorrect value
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+        //
+        // The trimming, parsing and range check all happen in `parse_guess`, which returns a
+        // `GuessError` saying what was wrong with the input instead of just ignoring it. The `Err`
+        // arm shows the player that error and `continue`s with the next iteration of the loop, so
+        // from here on we can proceed with the comparisons knowing that the guess is a number in
+        // range
+        let guess = match parse_guess(&guess, low, high) {
+            Ok(guess) => guess,
+            Err(error) => {
+                println!("{}", error);
+                continue;
+            }
         };
 
-        // The `if` expression checks whether our value is out of range, tells the user about the
-        // problem, and calls `continue`, can proceed with the comparisons `guess` and the secret
-        // number knowing that `guess` is between 1 and 100
-        if guess < 1 || guess > 100 {
-            println!("The secret number will be between 1 and 100.");
-            continue;
-        }
-
         println!("You guessed: {}", guess);
+        guesses_made += 1;
 
         // the `cmp()` method compares two values and can be called on anything that can be compared
         // It takes a reference to whatever you want to compare with, here it is comparing the `guess`
@@ -94,7 +122,7 @@ fn main() {
         // arm's pattern. Rust takes the value given to `match` and looks through each arm's pattern
         // in turn. The `match` construct and patterns are powerful features in Rust that let you express
         // a variety of situations your code might encounter and make sure that your handle them all.
-        match guess.cmp(&secret_number) {
+        match evaluate_guess(guess, secret_number) {
             Ordering::Less => println!("Too small!"),
             Ordering::Greater => println!("Too big!"),
             Ordering::Equal => {
@@ -105,5 +133,119 @@ fn main() {
                 break;
             }
         }
+
+        if attempts_remaining(MAX_ATTEMPTS, guesses_made) == 0 {
+            println!("You lose! The number was {}", secret_number);
+            break;
+        }
+    }
+}
+
+// Picks the secret number between `low` and `high`, both included. The upper bound passed to
+// `gen_range` is exclusive, so we add 1 to make `high` a possible secret number
+fn pick_secret(rng: &mut impl Rng, low: u32, high: u32) -> u32 {
+    rng.gen_range(low, high + 1)
+}
+
+// Comparing the guess with the secret number doesn't need any input or output, so it lives in its
+// own function where it can be tested
+fn evaluate_guess(guess: u32, secret: u32) -> Ordering {
+    guess.cmp(&secret)
+}
+
+// How many guesses are left after `guesses_made` of `max_attempts` have been used. `saturating_sub`
+// stops at 0 instead of underflowing if we're ever asked about more guesses than allowed.
+fn attempts_remaining(max_attempts: u32, guesses_made: u32) -> u32 {
+    max_attempts.saturating_sub(guesses_made)
+}
+
+// Turns the player's answer to the difficulty prompt into the `(low, high)` range the secret number
+// is picked from. The answer can be the name of the level or its number, in any case and with any
+// surrounding whitespace. Anything else falls back to medium, after telling the player so.
+fn parse_difficulty(input: &str) -> (u32, u32) {
+    match input.trim().to_lowercase().as_str() {
+        "easy" | "1" => (1, 50),
+        "medium" | "2" => (1, 100),
+        "hard" | "3" => (1, 500),
+        _ => {
+            println!("Unknown difficulty, playing on medium.");
+            (1, 100)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn easy_difficulty() {
+        assert_eq!((1, 50), parse_difficulty("easy\n"));
+        assert_eq!((1, 50), parse_difficulty("1"));
+    }
+
+    #[test]
+    fn medium_difficulty() {
+        assert_eq!((1, 100), parse_difficulty("Medium\n"));
+        assert_eq!((1, 100), parse_difficulty("2"));
+    }
+
+    #[test]
+    fn hard_difficulty() {
+        assert_eq!((1, 500), parse_difficulty("  HARD  "));
+        assert_eq!((1, 500), parse_difficulty("3"));
+    }
+
+    #[test]
+    fn guess_too_small() {
+        assert_eq!(Ordering::Less, evaluate_guess(10, 42));
+    }
+
+    #[test]
+    fn guess_too_big() {
+        assert_eq!(Ordering::Greater, evaluate_guess(50, 42));
+    }
+
+    #[test]
+    fn guess_correct() {
+        assert_eq!(Ordering::Equal, evaluate_guess(42, 42));
+    }
+
+    #[test]
+    fn attempts_count_down_to_zero() {
+        let mut remaining = Vec::new();
+        for guesses_made in 0..=MAX_ATTEMPTS {
+            remaining.push(attempts_remaining(MAX_ATTEMPTS, guesses_made));
+        }
+        assert_eq!(vec![7, 6, 5, 4, 3, 2, 1, 0], remaining);
+        assert_eq!(0, attempts_remaining(MAX_ATTEMPTS, MAX_ATTEMPTS + 1));
+    }
+
+    #[test]
+    fn unknown_difficulty_defaults_to_medium() {
+        assert_eq!((1, 100), parse_difficulty("impossible"));
+        assert_eq!((1, 100), parse_difficulty(""));
+    }
+
+    #[test]
+    fn same_seed_picks_the_same_secret() {
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+        assert_eq!(
+            pick_secret(&mut first, 1, 100),
+            pick_secret(&mut second, 1, 100)
+        );
+    }
+
+    #[test]
+    fn secret_stays_in_range() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            let secret = pick_secret(&mut rng, 1, 50);
+            assert!((1..=50).contains(&secret), "{} is out of range", secret);
+        }
+        assert_eq!(5, pick_secret(&mut rng, 5, 5));
     }
 }
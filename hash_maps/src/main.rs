@@ -12,6 +12,7 @@
 // starts with 10 points, and the Yellow team starts with 50.
 // Need to bring HashMap namespace into scope
 use std::collections::HashMap;
+use std::hash::Hash;
 
 // Like vectors, hash maps store their data on the heap. Keys must be of the same type, and values
 // must also be the same type
@@ -131,6 +132,15 @@ fn insert_if_key_not_exist() {
 // value 0
 fn update_value_based_on_old_value() {
     let text = "hello world wonderful world";
+    println!("{:?}", word_count(text));
+}
+
+// The counting above is useful enough to keep around, but printing the hashmap directly shows the
+// words in a different order from run to run, because a hashmap doesn't keep its keys in any
+// particular order. `word_count` does the same counting and then moves the pairs into a vector sorted
+// by count, highest first, with words that have the same count sorted alphabetically. For the text
+// above it returns `[("world", 2), ("hello", 1), ("wonderful", 1)]` every time.
+fn word_count(text: &str) -> Vec<(String, u32)> {
     let mut map = HashMap::new();
     for word in text.split_whitespace() {
         // The `or_insert` method actually returns a mutable/exclusive reference (`&mut v`) to the
@@ -142,8 +152,144 @@ fn update_value_based_on_old_value() {
         // The mutable/exclusive reference goes out of scope at the end of the `for` loop, here. So
         // all of these changes are safe and allowed by the barrowing rules.
     }
-    // This code will print `{"world": 2, "wonderful": 1, "hello": 1}`
-    println!("{:?}", map);
+    // Printing `map` here would show something like `{"world": 2, "wonderful": 1, "hello": 1}`
+
+    let mut counts: Vec<(String, u32)> = map
+        .into_iter()
+        .map(|(word, count)| (word.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+// `sorted_histogram` is the same idea for items that are already split up. Instead of
+// `or_insert(0)` it uses `or_default`, which inserts the default value for the value's type when the
+// key is missing; for a `usize` that's 0. The result is sorted the same way as `word_count`'s.
+#[allow(dead_code)]
+fn sorted_histogram(items: &[&str]) -> Vec<(String, usize)> {
+    let mut map: HashMap<&str, usize> = HashMap::new();
+    for item in items {
+        *map.entry(item).or_default() += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = map
+        .into_iter()
+        .map(|(item, count)| (item.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+// Sometimes there are two hashmaps to bring together, like the scores each team earned in the first
+// and second halves of a game. `merge_maps` takes ownership of both maps. Keys found in only one map
+// are carried over unchanged, and for keys found in both, the `combine` closure decides what the
+// merged value is (adding the two scores, keeping the larger one, and so on).
+#[allow(dead_code)]
+fn merge_maps<K: Eq + Hash + Clone, V, F: Fn(&V, &V) -> V>(
+    a: HashMap<K, V>,
+    mut b: HashMap<K, V>,
+    combine: F,
+) -> HashMap<K, V> {
+    let mut merged = HashMap::new();
+    for (key, a_value) in a {
+        // `remove` hands back `b`'s value for this key, so whatever is left in `b` afterwards is
+        // only in `b`
+        let value = match b.remove(&key) {
+            Some(b_value) => combine(&a_value, &b_value),
+            None => a_value,
+        };
+        merged.insert(key, value);
+    }
+    merged.extend(b);
+    merged
+}
+
+// The `entry` API is also the easiest way to sort items into buckets. `group_by` takes ownership
+// of `items` and moves each one into the bucket for the key that `key` computes for it. The first
+// item seen for a key creates an empty vector with `or_insert_with(Vec::new)`; `or_insert_with`
+// takes a closure, so the vector is only created when the key is missing. Items are pushed in the
+// order they appear in `items`, so each bucket keeps that order.
+#[allow(dead_code)]
+fn group_by<T, K: Eq + Hash, F: Fn(&T) -> K>(items: Vec<T>, key: F) -> HashMap<K, Vec<T>> {
+    let mut groups = HashMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_insert_with(Vec::new).push(item);
+    }
+    groups
+}
+
+// The `zip` and `collect` pattern from `create_hashmap_with_collect` is worth keeping as a function.
+// `zip` stops as soon as either iterator runs out, so when there are more keys than values (or more
+// values than keys) the extras are simply left out of the map rather than causing a panic. If the
+// same key shows up more than once, the value that comes later wins.
+#[allow(dead_code)]
+fn zip_to_map<K: Eq + Hash, V>(keys: Vec<K>, values: Vec<V>) -> HashMap<K, V> {
+    keys.into_iter().zip(values).collect()
+}
+
+// Counting words is one kind of aggregation; adding up amounts per category is another that comes
+// up all the time, like totaling expenses by type. It's the same `entry(...).or_insert(0)` pattern
+// as in `word_count`, except we add each record's amount instead of 1. The key has to be cloned
+// because the records are only borrowed, and the map needs to own its keys.
+#[allow(dead_code)]
+fn sum_by_category(records: &[(String, i32)]) -> HashMap<String, i32> {
+    let mut totals = HashMap::new();
+    for (category, amount) in records {
+        *totals.entry(category.clone()).or_insert(0) += amount;
+    }
+    totals
+}
+
+// Two words are anagrams when they're made of the same letters, like "eat" and "tea". Sorting the
+// characters of each word gives every anagram the same "signature" ("aet" for both), which makes a
+// good `HashMap` key. Rather than keeping the groups themselves in the map, where they'd come back in
+// whatever order the map iterates in, the map stores the index of each signature's group in
+// `groups`. That way the groups come out in the order their first word appears in `words`, and the
+// words in each group keep their order as well.
+#[allow(dead_code)]
+fn group_anagrams(words: Vec<String>) -> Vec<Vec<String>> {
+    let mut index_of: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<Vec<String>> = Vec::new();
+
+    for word in words {
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.sort_unstable();
+        let signature: String = chars.into_iter().collect();
+
+        let index = *index_of.entry(signature).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[index].push(word);
+    }
+
+    groups
+}
+
+// A simple config file is a list of `key=value` lines, which maps naturally onto a `HashMap`.
+// `parse_key_values` trims the whitespace around keys and values, skips blank lines and lines that
+// start with `#` (comments), and splits each remaining line at its first `=`, so a value may contain
+// `=` itself. A line without any `=` makes the whole input invalid, and the error says which line it
+// was. If a key appears twice, the later value overwrites the earlier one, as with `insert`.
+#[allow(dead_code)]
+fn parse_key_values(input: &str) -> Result<HashMap<String, String>, String> {
+    let mut map = HashMap::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.split_once('=') {
+            Some((key, value)) => {
+                map.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            None => return Err(format!("line {} is missing an '=': {}", i + 1, line)),
+        }
+    }
+
+    Ok(map)
 }
 
 // Hashing Functions
@@ -165,3 +311,226 @@ fn main() {
     insert_if_key_not_exist();
     update_value_based_on_old_value();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_count_sorted_by_count_then_word() {
+        assert_eq!(
+            vec![
+                (String::from("a"), 3),
+                (String::from("b"), 2),
+                (String::from("c"), 1),
+            ],
+            word_count("a b a c b a")
+        );
+    }
+
+    #[test]
+    fn word_count_breaks_ties_alphabetically() {
+        assert_eq!(
+            vec![
+                (String::from("world"), 2),
+                (String::from("hello"), 1),
+                (String::from("wonderful"), 1),
+            ],
+            word_count("hello world wonderful world")
+        );
+    }
+
+    #[test]
+    fn word_count_empty_text() {
+        assert!(word_count("").is_empty());
+    }
+
+    fn scores(pairs: &[(&str, i32)]) -> HashMap<String, i32> {
+        pairs
+            .iter()
+            .map(|&(team, score)| (String::from(team), score))
+            .collect()
+    }
+
+    #[test]
+    fn merge_maps_summing_scores() {
+        let first_half = scores(&[("Blue", 10), ("Yellow", 50)]);
+        let second_half = scores(&[("Blue", 15), ("Yellow", 5)]);
+        assert_eq!(
+            scores(&[("Blue", 25), ("Yellow", 55)]),
+            merge_maps(first_half, second_half, |a, b| a + b)
+        );
+    }
+
+    #[test]
+    fn merge_maps_keeping_the_max() {
+        let first_half = scores(&[("Blue", 10), ("Yellow", 50)]);
+        let second_half = scores(&[("Blue", 15), ("Yellow", 5)]);
+        assert_eq!(
+            scores(&[("Blue", 15), ("Yellow", 50)]),
+            merge_maps(first_half, second_half, |a, b| *a.max(b))
+        );
+    }
+
+    #[test]
+    fn merge_maps_disjoint_keys_pass_through() {
+        let first_half = scores(&[("Blue", 10), ("Red", 3)]);
+        let second_half = scores(&[("Blue", 15), ("Green", 7)]);
+        assert_eq!(
+            scores(&[("Blue", 25), ("Red", 3), ("Green", 7)]),
+            merge_maps(first_half, second_half, |a, b| a + b)
+        );
+        assert_eq!(
+            scores(&[("Green", 7)]),
+            merge_maps(HashMap::new(), scores(&[("Green", 7)]), |a, b| a + b)
+        );
+    }
+
+    #[test]
+    fn zip_to_map_equal_lengths() {
+        let map = zip_to_map(
+            vec![String::from("Blue"), String::from("Yellow")],
+            vec![10, 50],
+        );
+        assert_eq!(scores(&[("Blue", 10), ("Yellow", 50)]), map);
+    }
+
+    #[test]
+    fn zip_to_map_more_keys_than_values() {
+        let map = zip_to_map(
+            vec![
+                String::from("Blue"),
+                String::from("Yellow"),
+                String::from("Red"),
+            ],
+            vec![10, 50],
+        );
+        assert_eq!(2, map.len());
+        assert_eq!(scores(&[("Blue", 10), ("Yellow", 50)]), map);
+    }
+
+    #[test]
+    fn zip_to_map_more_values_than_keys() {
+        let map = zip_to_map(vec![String::from("Blue")], vec![10, 50, 70]);
+        assert_eq!(1, map.len());
+        assert_eq!(scores(&[("Blue", 10)]), map);
+    }
+
+    #[test]
+    fn sum_by_category_adds_amounts_per_key() {
+        let records = vec![
+            (String::from("a"), 1),
+            (String::from("b"), 2),
+            (String::from("a"), 3),
+        ];
+        assert_eq!(scores(&[("a", 4), ("b", 2)]), sum_by_category(&records));
+    }
+
+    #[test]
+    fn sum_by_category_empty() {
+        assert!(sum_by_category(&[]).is_empty());
+    }
+
+    #[test]
+    fn group_by_parity() {
+        let groups = group_by(vec![1, 2, 3, 4, 5, 6, 7], |n| n % 2 == 0);
+        assert_eq!(2, groups.len());
+        assert_eq!(Some(&vec![2, 4, 6]), groups.get(&true));
+        assert_eq!(Some(&vec![1, 3, 5, 7]), groups.get(&false));
+    }
+
+    #[test]
+    fn group_by_first_character() {
+        let words = vec![
+            "apple",
+            "banana",
+            "avocado",
+            "blueberry",
+            "cherry",
+            "apricot",
+        ];
+        let groups = group_by(words, |word| word.chars().next());
+        assert_eq!(3, groups.len());
+        assert_eq!(
+            Some(&vec!["apple", "avocado", "apricot"]),
+            groups.get(&Some('a'))
+        );
+        assert_eq!(Some(&vec!["banana", "blueberry"]), groups.get(&Some('b')));
+        assert_eq!(Some(&vec!["cherry"]), groups.get(&Some('c')));
+    }
+
+    #[test]
+    fn group_by_nothing() {
+        let groups = group_by(Vec::<i32>::new(), |n| *n);
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn group_anagrams_keeps_first_seen_order() {
+        let words = ["eat", "tea", "tan", "ate", "nat", "bat"]
+            .iter()
+            .map(|&w| String::from(w))
+            .collect();
+        assert_eq!(
+            vec![vec!["eat", "tea", "ate"], vec!["tan", "nat"], vec!["bat"],],
+            group_anagrams(words)
+        );
+    }
+
+    #[test]
+    fn group_anagrams_of_nothing() {
+        assert!(group_anagrams(Vec::new()).is_empty());
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|&(key, value)| (String::from(key), String::from(value)))
+            .collect()
+    }
+
+    #[test]
+    fn parse_key_values_multiple_lines() {
+        let input = "name = minigrep\nversion=0.1.0\n  query = a=b  ";
+        assert_eq!(
+            Ok(pairs(&[
+                ("name", "minigrep"),
+                ("version", "0.1.0"),
+                ("query", "a=b"),
+            ])),
+            parse_key_values(input)
+        );
+    }
+
+    #[test]
+    fn parse_key_values_skips_comments_and_blank_lines() {
+        let input = "# settings\n\ncolor = true\n   \n  # case_insensitive = true\n";
+        assert_eq!(Ok(pairs(&[("color", "true")])), parse_key_values(input));
+    }
+
+    #[test]
+    fn parse_key_values_malformed_line() {
+        let input = "color = true\njust some words\n";
+        assert_eq!(
+            Err(String::from("line 2 is missing an '=': just some words")),
+            parse_key_values(input)
+        );
+    }
+
+    #[test]
+    fn sorted_histogram_by_count_then_key() {
+        assert_eq!(
+            vec![
+                (String::from("b"), 3),
+                (String::from("a"), 2),
+                (String::from("c"), 1),
+            ],
+            sorted_histogram(&["b", "a", "b", "c", "a", "b"])
+        );
+    }
+
+    #[test]
+    fn sorted_histogram_of_nothing() {
+        assert!(sorted_histogram(&[]).is_empty());
+    }
+}
@@ -58,6 +58,8 @@ fn example_iter() {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn iterator_demonstration() {
         let v1 = vec![1, 2, 3];
@@ -104,6 +106,144 @@ mod tests {
 
         assert_eq!(total, 6);
     }
+
+    #[test]
+    fn calling_next_directly() {
+        let mut counter = Counter::new();
+
+        assert_eq!(counter.next(), Some(1));
+        assert_eq!(counter.next(), Some(2));
+        assert_eq!(counter.next(), Some(3));
+        assert_eq!(counter.next(), Some(4));
+        assert_eq!(counter.next(), Some(5));
+        assert_eq!(counter.next(), None);
+    }
+
+    /*
+        Because `Counter` implements `Iterator`, every other `Iterator` method comes for free. Here
+        we pair the values of one `Counter` with the values of another `Counter` that skips its
+        first value, multiply each pair together, keep only the results that are divisible by 3,
+        and add those up. `zip` stops after four pairs because the second iterator runs out first:
+        (1, 2), (2, 3), (3, 4), (4, 5) become 2, 6, 12, 20, and 6 + 12 = 18.
+    */
+    #[test]
+    fn using_other_iterator_trait_methods() {
+        let sum: u32 = Counter::new()
+            .zip(Counter::new().skip(1))
+            .map(|(a, b)| a * b)
+            .filter(|x| x % 3 == 0)
+            .sum();
+        assert_eq!(18, sum);
+    }
+
+    #[test]
+    fn char_histogram_counts_non_whitespace() {
+        let counts = char_histogram("hello world");
+        assert_eq!(Some(&3), counts.get(&'l'));
+        assert_eq!(Some(&2), counts.get(&'o'));
+        assert_eq!(Some(&1), counts.get(&'h'));
+        assert_eq!(None, counts.get(&' '));
+        assert_eq!(7, counts.len());
+    }
+
+    #[test]
+    fn top_char_is_most_frequent() {
+        assert_eq!(Some('l'), top_char("hello world"));
+        assert_eq!(Some('a'), top_char("abab"));
+    }
+
+    #[test]
+    fn fibonacci_first_ten() {
+        let first_ten: Vec<u64> = Fibonacci::new().take(10).collect();
+        assert_eq!(vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34], first_ten);
+    }
+
+    #[test]
+    fn fibonacci_stops_before_overflowing() {
+        // fib(0) through fib(93) fit in a u64, fib(94) doesn't
+        assert_eq!(94, Fibonacci::new().count());
+        assert_eq!(Some(12_200_160_415_121_876_738), Fibonacci::new().last());
+
+        let mut fibonacci = Fibonacci::new().skip(94);
+        assert_eq!(None, fibonacci.next());
+        assert_eq!(None, fibonacci.next());
+    }
+
+    #[test]
+    fn parse_all_valid_skips_bad_inputs() {
+        let inputs = ["1", "x", "3", "-5", "9z"];
+        assert_eq!(vec![1, 3, -5], parse_all_valid(&inputs));
+        assert_eq!(-1, sum_valid(&inputs));
+    }
+
+    #[test]
+    fn rle_encode_runs() {
+        assert_eq!(vec![('a', 3), ('b', 2), ('c', 1)], rle_encode("aaabbc"));
+    }
+
+    #[test]
+    fn rle_round_trip() {
+        for &s in &["", "a", "aaabbc", "abcabc", "zzzzzzzzzz", "ℤℤ😻 ab"] {
+            assert_eq!(s, rle_decode(&rle_encode(s)));
+        }
+    }
+
+    #[test]
+    fn sum_with_log_records_each_element() {
+        assert_eq!((6, vec![1, 2, 3]), sum_with_log(&[1, 2, 3]));
+        assert_eq!((0, vec![]), sum_with_log(&[]));
+    }
+
+    #[test]
+    fn running_totals_accumulates() {
+        assert_eq!(vec![1, 3, 6], running_totals(&[1, 2, 3]));
+        assert_eq!(vec![-1, 1, -2, -2], running_totals(&[-1, 2, -3, 0]));
+        assert!(running_totals(&[]).is_empty());
+    }
+
+    #[test]
+    fn running_max_keeps_largest_so_far() {
+        assert_eq!(vec![1, 3, 3, 5], running_max(&[1, 3, 2, 5]));
+        assert_eq!(vec![-5, -2, -2], running_max(&[-5, -2, -7]));
+        assert!(running_max(&[]).is_empty());
+    }
+
+    #[test]
+    fn primes_first_five() {
+        let primes: Vec<u64> = Primes::new().take(5).collect();
+        assert_eq!(vec![2, 3, 5, 7, 11], primes);
+        assert_eq!(Some(2), Primes::new().next());
+        assert_eq!(Some(541), Primes::new().nth(99));
+    }
+
+    #[test]
+    fn primes_skip_composites() {
+        let primes: Vec<u64> = Primes::new().take(10).collect();
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29], primes);
+        assert!(!primes.contains(&9));
+    }
+
+    #[test]
+    fn moving_average_over_full_windows() {
+        assert_eq!(
+            vec![1.5, 2.5, 3.5],
+            moving_average(&[1.0, 2.0, 3.0, 4.0], 2)
+        );
+        assert_eq!(vec![2.5], moving_average(&[1.0, 2.0, 3.0, 4.0], 4));
+    }
+
+    #[test]
+    fn moving_average_without_a_full_window() {
+        assert!(moving_average(&[1.0, 2.0, 3.0], 4).is_empty());
+        assert!(moving_average(&[1.0, 2.0, 3.0], 0).is_empty());
+        assert!(moving_average(&[], 2).is_empty());
+    }
+
+    #[test]
+    fn histogram_of_empty_string() {
+        assert!(char_histogram("").is_empty());
+        assert_eq!(None, top_char(""));
+    }
 }
 
 /*
@@ -120,7 +260,279 @@ fn iterator_adaptor_with_consumer() {
     println!("{:?}", v2);
 }
 
+/*
+    Iterator adaptors and consumers can do a lot more than `map` and `sum`. `char_histogram` counts
+    how many times each character shows up in a string: `filter` drops the whitespace, and `fold`
+    consumes the iterator, starting with an empty `HashMap` as the accumulator and bumping the count
+    for each character it's handed.
+
+    `top_char` then iterates over that map and uses `max_by` to find the character with the highest
+    count. When characters are tied, the one that sorts first wins (so "abab" gives 'a'); otherwise
+    the answer would depend on the order the `HashMap` happens to return its entries in.
+*/
+use std::collections::HashMap;
+
+fn char_histogram(text: &str) -> HashMap<char, usize> {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .fold(HashMap::new(), |mut counts, c| {
+            *counts.entry(c).or_insert(0) += 1;
+            counts
+        })
+}
+
+fn top_char(text: &str) -> Option<char> {
+    char_histogram(text)
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(c, _)| c)
+}
+
+/*
+    `filter_map` combines `filter` and `map`: the closure returns an `Option`, the `Some` values are
+    kept (unwrapped), and the `None` values are dropped. Because `parse` returns a `Result`, calling
+    `ok()` on it turns a failed parse into `None`, so `parse_all_valid` keeps only the inputs that
+    really are numbers. `sum_valid` just consumes those with `sum`.
+*/
+fn parse_all_valid(inputs: &[&str]) -> Vec<i32> {
+    inputs.iter().filter_map(|s| s.parse().ok()).collect()
+}
+
+fn sum_valid(inputs: &[&str]) -> i32 {
+    parse_all_valid(inputs).iter().sum()
+}
+
+/*
+    Because adaptors are lazy, it can be hard to tell what a chain is actually doing. `inspect`
+    hands each item to a closure and then passes it along unchanged, which makes it handy for
+    debugging: `sum_with_log` pushes every element it visits onto a log while `sum` consumes the
+    iterator, so we can see both the result and the order the elements went by in.
+*/
+fn sum_with_log(v: &[i32]) -> (i32, Vec<i32>) {
+    let mut log = Vec::new();
+    let sum = v.iter().inspect(|&&x| log.push(x)).sum();
+    (sum, log)
+}
+
+/*
+    `scan` is like `fold`, except that instead of producing one value at the end it yields a value
+    for every item. It keeps a piece of state (the first argument) that the closure can change, and
+    whatever `Some` the closure returns is the next item of the new iterator. `running_totals` keeps
+    the sum so far as its state, so `[1, 2, 3]` becomes `[1, 3, 6]`; `running_max` keeps the largest
+    value seen so far instead.
+*/
+fn running_totals(v: &[i32]) -> Vec<i32> {
+    v.iter()
+        .scan(0, |total, &x| {
+            *total += x;
+            Some(*total)
+        })
+        .collect()
+}
+
+fn running_max(v: &[i32]) -> Vec<i32> {
+    v.iter()
+        .scan(i32::MIN, |max, &x| {
+            *max = (*max).max(x);
+            Some(*max)
+        })
+        .collect()
+}
+
+/*
+    Slices have iterator methods of their own. `windows(n)` yields every run of `n` neighbouring
+    elements as a smaller slice, moving one element along each time, so `[1.0, 2.0, 3.0, 4.0]` with
+    a window of 2 gives `[1.0, 2.0]`, `[2.0, 3.0]` and `[3.0, 4.0]`. `moving_average` maps each
+    window to its average, which gives one value per full window: none at all when the window is
+    bigger than the data. `windows` panics if asked for windows of size 0, so that case returns an
+    empty vector before it gets there.
+*/
+fn moving_average(data: &[f64], window: usize) -> Vec<f64> {
+    if window == 0 {
+        return Vec::new();
+    }
+    data.windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+/*
+    Sometimes we need to drive an iterator by hand. Calling `peekable` wraps an iterator so we can
+    look at the next item with `peek` without consuming it. `rle_encode` run-length encodes a string
+    this way: it takes one character with `next`, then keeps consuming characters for as long as
+    `peek` shows the same one coming up, counting as it goes.
+
+    `rle_decode` goes the other way: `repeat` makes an endless iterator of one character, and `take`
+    cuts it off after as many as the run says. (Newer versions of Rust have `repeat_n`, which does
+    both at once and which Clippy points us to.)
+*/
+fn rle_encode(s: &str) -> Vec<(char, usize)> {
+    let mut runs = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let mut count = 1;
+        while chars.peek() == Some(&c) {
+            chars.next();
+            count += 1;
+        }
+        runs.push((c, count));
+    }
+
+    runs
+}
+
+#[allow(clippy::manual_repeat_n)]
+fn rle_decode(runs: &[(char, usize)]) -> String {
+    runs.iter()
+        .flat_map(|&(c, count)| std::iter::repeat(c).take(count))
+        .collect()
+}
+
+/*
+    Creating Our Own Iterators with the Iterator Trait
+
+    We can create iterators that do anything we want by implementing the `Iterator` trait on our
+    own types. The only method we're required to provide a definition for is `next`. Once we've done
+    that, we can use all the other methods that have default implementations provided by the
+    `Iterator` trait.
+
+    `Counter` has one field named `count`, which holds a `u32` value that keeps track of where we
+    are in the process of iterating from 1 to 5. The field is private because we want the
+    implementation of `Counter` to manage its value. `Counter::new` always starts new instances
+    with a value of 0 in the `count` field.
+*/
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+/*
+    We set the associated `Item` type for our iterator to `u32`, meaning the iterator will return
+    `u32` values. Each call to `next` increments the current value by 1 and wraps it in `Some`, so
+    the first call returns 1. Once `count` has reached 5, `next` returns `None` and keeps doing so.
+*/
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < 5 {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+/*
+    Iterators don't have to stop after a fixed number of items. `Fibonacci` keeps producing the next
+    number in the sequence for as long as it fits in a `u64`. Each call to `next` returns `curr` and
+    moves both fields one step along. The fields are `Option`s because `checked_add` returns `None`
+    once a sum would overflow; when that happens the `None` works its way into `curr` and the
+    iterator ends, instead of panicking or wrapping around to a wrong number.
+*/
+struct Fibonacci {
+    curr: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    fn new() -> Fibonacci {
+        Fibonacci {
+            curr: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.curr?;
+        self.curr = self.next;
+        self.next = self.next.and_then(|next| value.checked_add(next));
+        Some(value)
+    }
+}
+
+/*
+    An iterator can also remember what it has produced so far. `Primes` yields the prime numbers in
+    order, working each one out only when `next` is called. To check whether a candidate is prime we
+    only need to try dividing it by the primes we've already found, and only by those up to its
+    square root: if it had a larger factor, it would also have a smaller one we've already tried.
+    "Dividing" here means checking for a remainder with `%`; Clippy's suggested `is_multiple_of` is
+    too new for the Rust versions these examples target.
+*/
+struct Primes {
+    found: Vec<u64>,
+    candidate: u64,
+}
+
+impl Primes {
+    fn new() -> Primes {
+        Primes {
+            found: Vec::new(),
+            candidate: 2,
+        }
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    #[allow(clippy::manual_is_multiple_of)]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let candidate = self.candidate;
+            self.candidate += 1;
+
+            let is_prime = self
+                .found
+                .iter()
+                .take_while(|&&p| p * p <= candidate)
+                .all(|&p| candidate % p != 0);
+            if is_prime {
+                self.found.push(candidate);
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+fn using_counter() {
+    for value in Counter::new() {
+        println!("Counter: {}", value);
+    }
+}
+
 fn main() {
     example_iter();
     iterator_adaptor_with_consumer();
+    using_counter();
+    let fibonacci: Vec<u64> = Fibonacci::new().take(10).collect();
+    println!("Fibonacci: {:?}", fibonacci);
+    let primes: Vec<u64> = Primes::new().take(10).collect();
+    println!("Primes: {:?}", primes);
+    println!("Most common character: {:?}", top_char("hello world"));
+    println!("Sum of the numbers: {}", sum_valid(&["1", "two", "3"]));
+    println!("Sum with log: {:?}", sum_with_log(&[1, 2, 3]));
+    println!("Running totals: {:?}", running_totals(&[1, 2, 3]));
+    println!("Running max: {:?}", running_max(&[1, 3, 2, 5]));
+    println!(
+        "Moving average: {:?}",
+        moving_average(&[1.0, 2.0, 3.0, 4.0], 2)
+    );
+    let runs = rle_encode("aaabbc");
+    println!(
+        "Run-length encoded: {:?}, decoded: {}",
+        runs,
+        rle_decode(&runs)
+    );
 }
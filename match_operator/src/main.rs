@@ -13,6 +13,7 @@
 // We can write a function that can take an unknown United States coin and, in a similar way as the
 // counting machine, determine which coin it is and return its value in cents
 #[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
 enum Coin {
     Penny,
     Nickel,
@@ -20,8 +21,10 @@ enum Coin {
     Quarter,
 }
 
+// `value_in_cents` only needs to look at the coin, so it borrows it. Matching on a reference still
+// lets us write the patterns as `Coin::Penny` and so on; Rust takes care of the `&` for us.
 #[allow(dead_code)]
-fn value_in_cents(coin: Coin) -> u8 {
+fn value_in_cents(coin: &Coin) -> u8 {
     // first we list the `match` keyword followed by an expression, `coin` in this case
     match coin {
         // Next are the `match` arms. An arm has two parts: a pattern and some code. This first arm
@@ -59,6 +62,58 @@ fn value_in_cents2(coin: Coin) -> u8 {
     }
 }
 
+// An associated function can hand back one of every kind of coin, which is handy when we want to go
+// through all of them
+//
+// `from_cents` goes the other way from `value_in_cents`. A `match` on a number has to cover every
+// possible u8, not just the four that are coins, so the last arm uses the `_` placeholder to match
+// everything else, and since most numbers aren't the value of a coin we return an `Option`.
+#[allow(dead_code)]
+impl Coin {
+    fn all() -> Vec<Coin> {
+        vec![Coin::Penny, Coin::Nickel, Coin::Dime, Coin::Quarter]
+    }
+
+    fn from_cents(cents: u8) -> Option<Coin> {
+        match cents {
+            1 => Some(Coin::Penny),
+            5 => Some(Coin::Nickel),
+            10 => Some(Coin::Dime),
+            25 => Some(Coin::Quarter),
+            _ => None,
+        }
+    }
+}
+
+// Because `value_in_cents` borrows the coin, we can add up a slice of coins without taking ownership
+// of them. We widen each value to a u32 so a big pile of coins doesn't overflow a u8.
+#[allow(dead_code)]
+fn total_value(coins: &[Coin]) -> u32 {
+    coins
+        .iter()
+        .map(|coin| u32::from(value_in_cents(coin)))
+        .sum()
+}
+
+// Making change is the coin-sorting machine run backwards. Going from the most valuable coin to the
+// least (`Coin::all` lists them the other way around, so we reverse it), we hand out as many of each
+// coin as still fit into the amount that's left. For US coins this "greedy" approach always uses the
+// fewest coins possible.
+#[allow(dead_code)]
+fn make_change(mut amount: u8) -> Vec<Coin> {
+    let mut change = Vec::new();
+
+    for coin in Coin::all().into_iter().rev() {
+        let value = value_in_cents(&coin);
+        while amount >= value {
+            amount -= value;
+            change.push(coin.clone());
+        }
+    }
+
+    change
+}
+
 // Another useful feature of match arms is that they can bind to the parts of the values that match
 // the pattern. This is how we can extract values out of enum variants
 //
@@ -66,7 +121,7 @@ fn value_in_cents2(coin: Coin) -> u8 {
 // 2008, the United States minted quarters with different designs for each of the 50 states on one
 // side. No other coins got state designs, so only quarters have this extra value. We can add this
 // information to `enum` by changing the `Quarter` variant to include a `UsState` value stored inside
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Hash)]
 #[allow(dead_code)]
 enum UsState {
     Alabama,
@@ -100,6 +155,33 @@ fn value_in_cents_state_quarter_added(coin: Coin2) -> u8 {
     }
 }
 
+// To help our friend out even more, we can sort a whole handful of change at once: add up its value
+// and collect every state we saw on a quarter. A `HashSet` only keeps one of each value, so a state
+// we see twice is only listed once. Storing `UsState` values in a `HashSet` requires them to be
+// comparable and hashable, which is why `UsState` derives `PartialEq`, `Eq`, and `Hash`.
+//
+// The quarter arm takes the `state` out of the coin and moves it into the set; any other coin is
+// handed to `value_in_cents_state_quarter_added` to be counted as before.
+use std::collections::HashSet;
+
+#[allow(dead_code)]
+fn sort_change(coins: Vec<Coin2>) -> (u32, HashSet<UsState>) {
+    let mut total = 0;
+    let mut states = HashSet::new();
+
+    for coin in coins {
+        match coin {
+            Coin2::Quarter(state) => {
+                total += 25;
+                states.insert(state);
+            }
+            other => total += u32::from(value_in_cents_state_quarter_added(other)),
+        }
+    }
+
+    (total, states)
+}
+
 fn main() {
     // If we were to call
     let mut value = 0;
@@ -107,3 +189,89 @@ fn main() {
     value += value_in_cents_state_quarter_added(Coin2::Dime);
     println!("We have {} cents in coins.", &value);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_value_of_one_of_each_coin() {
+        assert_eq!(41, total_value(&Coin::all()));
+        assert_eq!(
+            41,
+            total_value(&[Coin::Penny, Coin::Nickel, Coin::Dime, Coin::Quarter])
+        );
+    }
+
+    #[test]
+    fn from_cents_round_trips() {
+        for coin in Coin::all() {
+            assert_eq!(
+                Some(&coin),
+                Coin::from_cents(value_in_cents(&coin)).as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn from_cents_of_a_non_coin() {
+        assert_eq!(None, Coin::from_cents(7));
+        assert_eq!(None, Coin::from_cents(0));
+    }
+
+    #[test]
+    fn make_change_for_99_cents() {
+        let change = make_change(99);
+        let count = |coin: Coin| change.iter().filter(|&c| *c == coin).count();
+        assert_eq!(3, count(Coin::Quarter));
+        assert_eq!(2, count(Coin::Dime));
+        assert_eq!(0, count(Coin::Nickel));
+        assert_eq!(4, count(Coin::Penny));
+        assert_eq!(9, change.len());
+        assert_eq!(99, total_value(&change));
+    }
+
+    #[test]
+    fn make_change_for_nothing() {
+        assert!(make_change(0).is_empty());
+    }
+
+    #[test]
+    fn make_change_for_one_nickel() {
+        let change = make_change(5);
+        assert_eq!(vec![Coin::Nickel], change);
+        assert_eq!(5, total_value(&change));
+    }
+
+    #[test]
+    fn total_value_of_no_coins() {
+        assert_eq!(0, total_value(&[]));
+    }
+
+    #[test]
+    fn sort_change_totals_coins_and_collects_states() {
+        let coins = vec![
+            Coin2::Quarter(UsState::Alaska),
+            Coin2::Penny,
+            Coin2::Quarter(UsState::Alabama),
+            Coin2::Dime,
+            Coin2::Quarter(UsState::Alaska),
+            Coin2::Nickel,
+        ];
+
+        let (total, states) = sort_change(coins);
+
+        assert_eq!(91, total);
+        let expected: HashSet<UsState> = vec![UsState::Alaska, UsState::Alabama]
+            .into_iter()
+            .collect();
+        assert_eq!(expected, states);
+    }
+
+    #[test]
+    fn sort_change_without_quarters() {
+        let (total, states) = sort_change(vec![Coin2::Penny, Coin2::Dime]);
+        assert_eq!(11, total);
+        assert!(states.is_empty());
+    }
+}
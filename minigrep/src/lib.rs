@@ -1,44 +1,594 @@
 use std::env;
 use std::error::Error;
 use std::fs;
+use std::io::{self, Read};
+
+use regex::{Captures, Regex, RegexBuilder};
+
+// How the query is compared against each line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchMode {
+    // the query is a plain substring
+    Literal,
+    // the query is a regular expression, turned on with `--regex`
+    Regex,
+}
 
 pub struct Config {
     pub query: String,
-    pub filename: String,
+    pub filenames: Vec<String>,
     pub case_insensitive: bool,
+    pub context: usize,
+    pub count_only: bool,
+    pub invert: bool,
+    pub color: bool,
+    pub line_numbers: bool,
+    pub match_mode: MatchMode,
+    pub replace: Option<String>,
+    pub output: Option<String>,
+    // stop after this many matching lines in each file, set with `--max`
+    pub max_results: Option<usize>,
+    // only match the query as a whole word, set with `-w`
+    pub word_match: bool,
+    // the query compiled once up front when `match_mode` is `MatchMode::Regex`
+    regex: Option<Regex>,
 }
 
+// ANSI escapes that turn the terminal text red and back again, as `grep --color=always` does
+const COLOR_START: &str = "\x1b[31m";
+const COLOR_END: &str = "\x1b[0m";
+
 impl Config {
-    pub fn new(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("not enough arguments");
+    // `Config::new` takes ownership of an iterator of `String`s (such as the one `env::args` returns)
+    // instead of borrowing a slice, so each argument can be moved into the `Config` without cloning.
+    // Flags may appear anywhere on the command line; the first argument that isn't a flag is the
+    // query and every one after it is a file to search.
+    pub fn new(mut args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
+        // the first value returned by `env::args` is the name of the program, which we don't need
+        args.next();
+
+        let mut context = 0;
+        let mut count_only = false;
+        let mut invert = false;
+        let mut color = false;
+        let mut line_numbers = false;
+        let mut match_mode = MatchMode::Literal;
+        let mut replace = None;
+        let mut output = None;
+        let mut max_results = None;
+        let mut word_match = false;
+        let mut query = None;
+        let mut filenames = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-C" => {
+                    let value = args.next().ok_or("missing value for -C")?;
+                    context = value
+                        .parse()
+                        .map_err(|_| "-C expects a non-negative number of lines")?;
+                }
+                "-c" | "--count" => count_only = true,
+                "-v" | "--invert-match" => invert = true,
+                "--color" => color = true,
+                "-n" | "--line-number" => line_numbers = true,
+                "--regex" => match_mode = MatchMode::Regex,
+                "-w" | "--word" => word_match = true,
+                "--replace" => {
+                    replace = Some(args.next().ok_or("missing value for --replace")?);
+                }
+                "-o" => output = Some(args.next().ok_or("missing value for -o")?),
+                "--max" => {
+                    let value = args.next().ok_or("missing value for --max")?;
+                    max_results = Some(
+                        value
+                            .parse()
+                            .map_err(|_| "--max expects a non-negative number of matches")?,
+                    );
+                }
+                _ if query.is_none() => query = Some(arg),
+                _ => filenames.push(arg),
+            }
         }
-        let query = args[1].clone();
-        let filename = args[2].clone();
-        let case_insensitive = env::var("CASE_INSENSITIVE").is_err();
+
+        let query = query.ok_or("Didn't get a query string")?;
+        if filenames.is_empty() {
+            return Err("Didn't get a file name");
+        }
+        // searching is case-sensitive unless the CASE_INSENSITIVE environment variable is set, to
+        // any value at all
+        let case_insensitive = env::var("CASE_INSENSITIVE").is_ok();
+        let regex = compile_regex(&query, match_mode, case_insensitive)
+            .map_err(|_| "the query given with --regex is not a valid regular expression")?;
         Ok(Config {
             query,
-            filename,
+            filenames,
             case_insensitive,
+            context,
+            count_only,
+            invert,
+            color,
+            line_numbers,
+            match_mode,
+            replace,
+            output,
+            max_results,
+            word_match,
+            regex,
+        })
+    }
+
+    // Whether `line` should be reported, taking case sensitivity and `-v` into account
+    pub fn is_match(&self, line: &str) -> bool {
+        let found = if let Some(regex) = &self.regex {
+            regex
+                .find_iter(line)
+                .any(|m| !self.word_match || is_whole_word(line, m.start(), m.end()))
+        } else if self.word_match && self.case_insensitive {
+            contains_word(&line.to_lowercase(), &self.query.to_lowercase())
+        } else if self.word_match {
+            contains_word(line, &self.query)
+        } else if self.case_insensitive {
+            line.to_lowercase().contains(&self.query.to_lowercase())
+        } else {
+            line.contains(&self.query)
+        };
+        found != self.invert
+    }
+
+    // How many matching lines to report per file; without `--max` there is no limit
+    fn limit(&self) -> usize {
+        self.max_results.unwrap_or(usize::MAX)
+    }
+
+    // How a reported line is printed: as-is, with every occurrence of the query highlighted, or with
+    // every occurrence replaced by `--replace`, and with its line number in front when `-n` was given
+    pub fn render(&self, number: usize, line: &str) -> String {
+        let line = if self.replace.is_some() {
+            self.replace_matches(line)
+        } else if !self.color {
+            line.to_string()
+        } else if self.regex.is_some() {
+            self.map_regex_matches(line, &format!("{}$0{}", COLOR_START, COLOR_END))
+        } else {
+            map_matches(
+                line,
+                &self.query,
+                self.case_insensitive,
+                self.word_match,
+                |found| format!("{}{}{}", COLOR_START, found, COLOR_END),
+            )
+        };
+        if self.line_numbers {
+            format!("{}:{}", number, line)
+        } else {
+            line
+        }
+    }
+
+    // `line` with every occurrence of the query swapped for the `--replace` text. In regex mode the
+    // replacement may refer to capture groups, such as `$1`.
+    pub fn replace_matches(&self, line: &str) -> String {
+        let replacement = match &self.replace {
+            Some(replacement) => replacement,
+            None => return line.to_string(),
+        };
+        match &self.regex {
+            Some(_) => self.map_regex_matches(line, replacement),
+            None => map_matches(
+                line,
+                &self.query,
+                self.case_insensitive,
+                self.word_match,
+                |_| replacement.to_string(),
+            ),
+        }
+    }
+
+    // Swaps every match of the regex in `line` for `replacement`, expanding `$0`, `$1` and so on.
+    // With `-w` a match that isn't a whole word is left as it was, the same as in literal mode.
+    fn map_regex_matches(&self, line: &str, replacement: &str) -> String {
+        let regex = match &self.regex {
+            Some(regex) => regex,
+            None => return line.to_string(),
+        };
+        regex
+            .replace_all(line, |caps: &Captures| {
+                let m = caps.get(0).unwrap();
+                if self.word_match && !is_whole_word(line, m.start(), m.end()) {
+                    return m.as_str().to_string();
+                }
+                let mut expanded = String::new();
+                caps.expand(replacement, &mut expanded);
+                expanded
+            })
+            .into_owned()
+    }
+}
+
+// Builds a `Config` from code rather than from command line arguments. Everything except the query
+// has a default: no context, no counting, inverting, color or line numbers, no limit on the number of
+// matches, a case-sensitive search, printing to stdout rather than to a file, and, if no files are
+// added, reading from stdin.
+#[derive(Default)]
+pub struct ConfigBuilder {
+    query: Option<String>,
+    filenames: Vec<String>,
+    case_insensitive: bool,
+    context: usize,
+    count_only: bool,
+    invert: bool,
+    color: bool,
+    line_numbers: bool,
+    match_mode: Option<MatchMode>,
+    replace: Option<String>,
+    max_results: Option<usize>,
+    word_match: bool,
+    output: Option<String>,
+}
+
+impl ConfigBuilder {
+    pub fn new() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    pub fn query(mut self, query: &str) -> ConfigBuilder {
+        self.query = Some(query.to_string());
+        self
+    }
+
+    pub fn add_file(mut self, filename: &str) -> ConfigBuilder {
+        self.filenames.push(filename.to_string());
+        self
+    }
+
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> ConfigBuilder {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    pub fn context(mut self, context: usize) -> ConfigBuilder {
+        self.context = context;
+        self
+    }
+
+    pub fn count_only(mut self, count_only: bool) -> ConfigBuilder {
+        self.count_only = count_only;
+        self
+    }
+
+    pub fn invert(mut self, invert: bool) -> ConfigBuilder {
+        self.invert = invert;
+        self
+    }
+
+    pub fn color(mut self, color: bool) -> ConfigBuilder {
+        self.color = color;
+        self
+    }
+
+    pub fn line_numbers(mut self, line_numbers: bool) -> ConfigBuilder {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    pub fn match_mode(mut self, match_mode: MatchMode) -> ConfigBuilder {
+        self.match_mode = Some(match_mode);
+        self
+    }
+
+    pub fn replace(mut self, replacement: &str) -> ConfigBuilder {
+        self.replace = Some(replacement.to_string());
+        self
+    }
+
+    pub fn max_results(mut self, max_results: usize) -> ConfigBuilder {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    pub fn word_match(mut self, word_match: bool) -> ConfigBuilder {
+        self.word_match = word_match;
+        self
+    }
+
+    pub fn output(mut self, path: &str) -> ConfigBuilder {
+        self.output = Some(path.to_string());
+        self
+    }
+
+    pub fn build(self) -> Result<Config, String> {
+        let query = match self.query {
+            Some(query) => query,
+            None => return Err(String::from("a query is required")),
+        };
+        let filenames = if self.filenames.is_empty() {
+            vec![String::from("-")]
+        } else {
+            self.filenames
+        };
+        let match_mode = self.match_mode.unwrap_or(MatchMode::Literal);
+        let regex = compile_regex(&query, match_mode, self.case_insensitive)
+            .map_err(|e| format!("invalid regular expression: {}", e))?;
+        Ok(Config {
+            query,
+            filenames,
+            case_insensitive: self.case_insensitive,
+            context: self.context,
+            count_only: self.count_only,
+            invert: self.invert,
+            color: self.color,
+            line_numbers: self.line_numbers,
+            match_mode,
+            replace: self.replace,
+            output: self.output,
+            max_results: self.max_results,
+            word_match: self.word_match,
+            regex,
         })
     }
 }
 
+// Compiles the query when it is meant to be a regular expression, so that an invalid pattern is
+// reported while building the `Config` rather than while searching
+fn compile_regex(
+    query: &str,
+    match_mode: MatchMode,
+    case_insensitive: bool,
+) -> Result<Option<Regex>, regex::Error> {
+    match match_mode {
+        MatchMode::Literal => Ok(None),
+        MatchMode::Regex => RegexBuilder::new(query)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map(Some),
+    }
+}
+
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.filename)?;
+    // `--replace` with `-o` rewrites whole files rather than reporting matching lines
+    if let (Some(_), Some(output)) = (&config.replace, &config.output) {
+        let mut rewritten = String::new();
+        for filename in &config.filenames {
+            rewritten.push_str(&replace_text(&config, &read_contents(filename)?));
+        }
+        return write_output(output, &rewritten);
+    }
 
-    let results = if config.case_insensitive {
-        search(&config.query, &contents)
-    } else {
-        search_case_insensitive(&config.query, &contents)
-    };
-    for line in results {
-        println!("{}", line);
+    let lines = run_collect(&config)?;
+    match &config.output {
+        Some(output) => {
+            let mut written = String::new();
+            for line in lines {
+                written.push_str(&line);
+                written.push('\n');
+            }
+            write_output(output, &written)
+        }
+        None => {
+            for line in lines {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+    }
+}
+
+// Everything `run` would print, across all of the files, returned instead of printed. This lets
+// minigrep be used as a library without having to capture stdout.
+pub fn run_collect(config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+    // with more than one file every line of output says which file it came from
+    let show_filename = config.filenames.len() > 1;
+
+    let mut lines = Vec::new();
+    for filename in &config.filenames {
+        let contents = read_contents(filename)?;
+        let label = if show_filename {
+            Some(filename.as_str())
+        } else {
+            None
+        };
+        lines.extend(output_lines(config, label, &contents));
     }
+    Ok(lines)
+}
 
+// Writes `contents` to the `-o` file. The `io::Error` on its own only says something like
+// "Permission denied", so the error that reaches `main` also says which file couldn't be written.
+fn write_output(path: &str, contents: &str) -> Result<(), Box<dyn Error>> {
+    fs::write(path, contents).map_err(|e| format!("could not write to {}: {}", path, e))?;
     Ok(())
 }
 
+// Exactly the lines `run` prints for one file's `contents`, with line numbers, color, context
+// separators and count mode all applied. Nothing is read from disk or written to stdout, so the
+// whole output pipeline can be checked from a test.
+pub fn search_text(config: &Config, contents: &str) -> Vec<String> {
+    output_lines(config, None, contents)
+}
+
+// The whole of `contents` with every line put through `Config::replace_matches`, keeping the lines
+// that don't match as they were
+pub fn replace_text(config: &Config, contents: &str) -> String {
+    let mut rewritten = String::with_capacity(contents.len());
+    for line in contents.lines() {
+        rewritten.push_str(&config.replace_matches(line));
+        rewritten.push('\n');
+    }
+    rewritten
+}
+
+// `search_text`, plus the filename in front of every line when searching more than one file
+fn output_lines(config: &Config, filename: Option<&str>, contents: &str) -> Vec<String> {
+    if config.count_only {
+        let count = count_matches(config, contents);
+        return match filename {
+            Some(filename) => vec![format!("{}: {}", filename, count)],
+            None => vec![count.to_string()],
+        };
+    }
+
+    let prefix = match filename {
+        Some(filename) => format!("{}:", filename),
+        None => String::new(),
+    };
+    let mut output = Vec::new();
+
+    if config.context > 0 {
+        let groups = group_with_context(contents, config.context, config.limit(), |line| {
+            config.is_match(line)
+        });
+        for (i, group) in groups.iter().enumerate() {
+            if i > 0 {
+                output.push(String::from("--"));
+            }
+            for &(number, line) in group {
+                output.push(format!("{}{}", prefix, config.render(number, line)));
+            }
+        }
+        return output;
+    }
+
+    // `take` stops pulling lines from `contents` as soon as the limit is reached, so the rest of a
+    // large file is never looked at
+    let matches = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| config.is_match(line))
+        .take(config.limit());
+    for (i, line) in matches {
+        output.push(format!("{}{}", prefix, config.render(i + 1, line)));
+    }
+    output
+}
+
+// Getting the contents is kept apart from searching them so the search can be tested on a plain
+// `&str`. A filename of exactly `-` means "read from stdin", which lets minigrep sit at the end of a
+// shell pipeline like `cat poem.txt | minigrep body -`.
+pub fn read_contents(filename: &str) -> Result<String, Box<dyn Error>> {
+    if filename == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        Ok(fs::read_to_string(filename)?)
+    }
+}
+
+// Every line of `contents` that `config` says should be reported
+pub fn matching_lines<'a>(config: &Config, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| config.is_match(line))
+        .take(config.limit())
+        .collect()
+}
+
+// The number of lines `run` would print for `contents`, used by `-c`
+pub fn count_matches(config: &Config, contents: &str) -> usize {
+    contents
+        .lines()
+        .filter(|line| config.is_match(line))
+        .take(config.limit())
+        .count()
+}
+
+// Whether `word` appears in `line` as a whole word, the way `grep -w` matches: the characters on
+// either side of the occurrence, if there are any, must not be letters or digits. So "cat" is found
+// in "the cat sat" but not in "category" or "bobcat". Every occurrence is checked, because the first
+// one might be part of a longer word while a later one stands alone.
+pub fn contains_word(line: &str, word: &str) -> bool {
+    line.match_indices(word)
+        .any(|(start, found)| is_whole_word(line, start, start + found.len()))
+}
+
+// Whether the text of `line` between the byte offsets `start` and `end` stands alone as a word. This
+// one check decides what `-w` means everywhere, in literal and regex mode alike, so an underscore
+// counts as a word boundary in both.
+fn is_whole_word(line: &str, start: usize, end: usize) -> bool {
+    let before = line[..start].chars().next_back();
+    let after = line[end..].chars().next();
+    !matches!(before, Some(c) if c.is_alphanumeric())
+        && !matches!(after, Some(c) if c.is_alphanumeric())
+}
+
+// Wraps every occurrence of `query` in `line` with color escapes. When matching case-insensitively
+// the highlighted text keeps its original casing; only the comparison ignores case. A line that
+// doesn't contain the query comes back unchanged.
+pub fn highlight(line: &str, query: &str, case_insensitive: bool) -> String {
+    map_matches(line, query, case_insensitive, false, |found| {
+        format!("{}{}{}", COLOR_START, found, COLOR_END)
+    })
+}
+
+// Replaces every occurrence of `query` in `line` with `replacement`, leaving the text around each
+// occurrence untouched. Like `highlight`, a case-insensitive query matches regardless of case.
+pub fn replace_in_line(
+    query: &str,
+    line: &str,
+    replacement: &str,
+    case_insensitive: bool,
+) -> String {
+    map_matches(line, query, case_insensitive, false, |_| {
+        replacement.to_string()
+    })
+}
+
+// Walks `line` from left to right, swapping every non-overlapping occurrence of `query` for
+// whatever `f` returns for it and copying everything else as-is. With `word_match` an occurrence
+// only counts when `is_whole_word` says it stands alone; `Config` passes `-w` through here, so
+// `highlight` and `replace_in_line` always match anywhere in the line.
+fn map_matches<F>(line: &str, query: &str, case_insensitive: bool, word_match: bool, f: F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    if query.is_empty() {
+        return line.to_string();
+    }
+
+    let mut mapped = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let start = line.len() - rest.len();
+        let found = prefix_match_len(rest, query, case_insensitive)
+            .filter(|&len| !word_match || is_whole_word(line, start, start + len));
+        let len = match found {
+            Some(len) => {
+                mapped.push_str(&f(&rest[..len]));
+                len
+            }
+            None => {
+                mapped.push(c);
+                c.len_utf8()
+            }
+        };
+        rest = &rest[len..];
+    }
+    mapped
+}
+
+// If `text` starts with `query`, how many bytes of `text` the match covers. Comparing one `char` at
+// a time (rather than lowercasing the whole line) keeps the byte offsets valid for `text` itself.
+fn prefix_match_len(text: &str, query: &str, case_insensitive: bool) -> Option<usize> {
+    if !case_insensitive {
+        return if text.starts_with(query) {
+            Some(query.len())
+        } else {
+            None
+        };
+    }
+
+    let mut text_chars = text.char_indices();
+    let mut len = 0;
+    for q in query.chars() {
+        match text_chars.next() {
+            Some((i, c)) if c.to_lowercase().eq(q.to_lowercase()) => len = i + c.len_utf8(),
+            _ => return None,
+        }
+    }
+    Some(len)
+}
+
 // Notice that we need an explicit lifetime `'a` defined in the signature of `search` and used with
 // the `contents` argument and the return value. Lifetime parameters specify which argument lifetime
 // is connected to the lifetime of the return value. In this case, we indicate that the returned
@@ -70,6 +620,85 @@ pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a st
     results
 }
 
+// Like `search`, but `pattern` is a regular expression. The pattern is compiled once, before looking
+// at any lines, and an invalid pattern is returned as an error.
+pub fn search_regex<'a>(pattern: &str, contents: &'a str) -> Result<Vec<&'a str>, regex::Error> {
+    let regex = Regex::new(pattern)?;
+    Ok(contents
+        .lines()
+        .filter(|line| regex.is_match(line))
+        .collect())
+}
+
+// Like `search`, but every match brings along up to `context` lines before and after it, the way
+// `grep -C` does. Each inner vector is one contiguous group of lines; when the windows of two
+// matches overlap or touch they are merged into a single group so that no line shows up twice.
+pub fn search_with_context<'a>(
+    query: &str,
+    contents: &'a str,
+    context: usize,
+    case_insensitive: bool,
+) -> Vec<Vec<&'a str>> {
+    if case_insensitive {
+        let query = query.to_lowercase();
+        strip_line_numbers(group_with_context(contents, context, usize::MAX, |line| {
+            line.to_lowercase().contains(&query)
+        }))
+    } else {
+        strip_line_numbers(group_with_context(contents, context, usize::MAX, |line| {
+            line.contains(query)
+        }))
+    }
+}
+
+fn strip_line_numbers(groups: Vec<Vec<(usize, &str)>>) -> Vec<Vec<&str>> {
+    groups
+        .into_iter()
+        .map(|group| group.into_iter().map(|(_, line)| line).collect())
+        .collect()
+}
+
+// The groups of lines behind `search_with_context`, with each line paired with its 1-based line
+// number so that `-n` can print it. Only the first `max_matches` matches are grouped; once the last
+// of them has had its trailing context read, the rest of `contents` is never looked at.
+fn group_with_context<F>(
+    contents: &str,
+    context: usize,
+    max_matches: usize,
+    mut is_match: F,
+) -> Vec<Vec<(usize, &str)>>
+where
+    F: FnMut(&str) -> bool,
+{
+    let mut lines: Vec<(usize, &str)> = Vec::new();
+    // half-open `start..end` ranges of line indexes, kept in order. `end` may run past the last
+    // line of `contents`; that's trimmed off when the groups are sliced out at the end.
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    let mut found = 0;
+
+    for (i, line) in contents.lines().enumerate() {
+        if found == max_matches && windows.last().map_or(0, |&(_, end)| end) <= i {
+            break;
+        }
+        lines.push((i + 1, line));
+        if found == max_matches || !is_match(line) {
+            continue;
+        }
+        found += 1;
+        let start = i.saturating_sub(context);
+        let end = i + context + 1;
+        match windows.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => windows.push((start, end)),
+        }
+    }
+
+    windows
+        .into_iter()
+        .map(|(start, end)| lines[start..end.min(lines.len())].to_vec())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +727,654 @@ Trust me.";
             search_case_insensitive(query, contents)
         );
     }
+
+    #[test]
+    fn context_at_start_of_file() {
+        let contents = "\
+one
+two
+three
+four
+five";
+        assert_eq!(
+            vec![vec!["one", "two", "three"]],
+            search_with_context("one", contents, 2, false)
+        );
+    }
+
+    #[test]
+    fn context_at_end_of_file() {
+        let contents = "\
+one
+two
+three
+four
+five";
+        assert_eq!(
+            vec![vec!["four", "five"]],
+            search_with_context("five", contents, 1, false)
+        );
+    }
+
+    #[test]
+    fn nearby_matches_merge_their_context() {
+        let contents = "\
+alpha
+match one
+beta
+match two
+gamma
+delta
+epsilon
+match three";
+        assert_eq!(
+            vec![
+                vec!["alpha", "match one", "beta", "match two", "gamma"],
+                vec!["epsilon", "match three"],
+            ],
+            search_with_context("match", contents, 1, false)
+        );
+    }
+
+    #[test]
+    fn context_flag_is_parsed() {
+        let config = config(&["-C", "2", "to", "poem.txt"]);
+        assert_eq!(2, config.context);
+        assert_eq!("to", config.query);
+        assert_eq!(vec!["poem.txt"], config.filenames);
+    }
+
+    #[test]
+    fn count_flag_and_multiple_files_are_parsed() {
+        let config = config(&["--count", "to", "a.txt", "b.txt"]);
+        assert!(config.count_only);
+        assert_eq!(vec!["a.txt", "b.txt"], config.filenames);
+    }
+
+    #[test]
+    fn count_matching_lines() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+        assert_eq!(
+            2,
+            count_matches(&config(&["-c", "ust", "poem.txt"]), contents)
+        );
+        assert_eq!(
+            0,
+            count_matches(&config(&["-c", "nope", "poem.txt"]), contents)
+        );
+    }
+
+    #[test]
+    fn count_with_invert_counts_non_matching_lines() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+        assert_eq!(
+            2,
+            count_matches(&config(&["-c", "-v", "ust", "poem.txt"]), contents)
+        );
+        assert_eq!(
+            4,
+            count_matches(&config(&["-c", "-v", "nope", "poem.txt"]), contents)
+        );
+    }
+
+    #[test]
+    fn dash_filename_is_kept_for_stdin() {
+        let config = config(&["body", "-"]);
+        assert_eq!(vec!["-"], config.filenames);
+    }
+
+    #[test]
+    fn search_piped_contents() {
+        // what `read_contents("-")` would hand back after `cat`-ing a file into minigrep
+        let piped = String::from("I'm nobody! Who are you?\nAre you nobody, too?\nHow dreary\n");
+        assert_eq!(
+            vec!["I'm nobody! Who are you?", "Are you nobody, too?"],
+            matching_lines(&config(&["nobody", "-"]), &piped)
+        );
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        assert!(read_contents("no-such-file.txt").is_err());
+    }
+
+    #[test]
+    fn highlight_every_occurrence() {
+        assert_eq!(
+            "\x1b[31mto\x1b[0m tell your name \x1b[31mto\x1b[0m",
+            highlight("to tell your name to", "to", false)
+        );
+    }
+
+    #[test]
+    fn highlight_case_insensitive_keeps_original_casing() {
+        assert_eq!(
+            "\x1b[31mRust\x1b[0m: \x1b[31mrUST\x1b[0m and T\x1b[31mrust\x1b[0m",
+            highlight("Rust: rUST and Trust", "rust", true)
+        );
+        assert_eq!("Rust: safe", highlight("Rust: safe", "rust", false));
+    }
+
+    #[test]
+    fn highlight_without_match_is_unchanged() {
+        assert_eq!("Pick three.", highlight("Pick three.", "duct", false));
+    }
+
+    #[test]
+    fn color_flag_is_parsed() {
+        let config = config(&["--color", "to", "poem.txt"]);
+        assert!(config.color);
+        assert_eq!("\x1b[31mto\x1b[0mad", config.render(1, "toad"));
+    }
+
+    #[test]
+    fn config_from_an_iterator() {
+        let args = vec![
+            String::from("minigrep"),
+            String::from("frog"),
+            String::from("poem.txt"),
+        ];
+        let config = Config::new(args.into_iter()).unwrap();
+        assert_eq!("frog", config.query);
+        assert_eq!(vec!["poem.txt"], config.filenames);
+    }
+
+    #[test]
+    fn config_missing_query() {
+        let args = vec![String::from("minigrep")];
+        assert_eq!(
+            Err("Didn't get a query string"),
+            Config::new(args.into_iter()).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn config_missing_filename() {
+        let args = vec![String::from("minigrep"), String::from("frog")];
+        assert_eq!(
+            Err("Didn't get a file name"),
+            Config::new(args.into_iter()).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn config_missing_context_value() {
+        let args = vec![String::from("minigrep"), String::from("-C")];
+        assert_eq!(
+            Err("missing value for -C"),
+            Config::new(args.into_iter()).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn line_number_flag_is_parsed() {
+        let config = config(&["-n", "to", "poem.txt"]);
+        assert!(config.line_numbers);
+        assert_eq!("3:toad", config.render(3, "toad"));
+    }
+
+    #[test]
+    fn builder_minimal_config_uses_defaults() {
+        let config = ConfigBuilder::new().query("frog").build().unwrap();
+        assert_eq!("frog", config.query);
+        assert_eq!(vec!["-"], config.filenames);
+        assert!(!config.case_insensitive);
+        assert_eq!(0, config.context);
+        assert!(!config.count_only);
+        assert!(!config.invert);
+        assert!(!config.color);
+        assert!(!config.line_numbers);
+        assert_eq!(None, config.output);
+    }
+
+    #[test]
+    fn builder_chained_setters() {
+        let config = ConfigBuilder::new()
+            .query("frog")
+            .add_file("poem.txt")
+            .add_file("other.txt")
+            .case_insensitive(true)
+            .line_numbers(true)
+            .context(2)
+            .build()
+            .unwrap();
+        assert_eq!(vec!["poem.txt", "other.txt"], config.filenames);
+        assert!(config.case_insensitive);
+        assert!(config.line_numbers);
+        assert_eq!(2, config.context);
+    }
+
+    #[test]
+    fn builder_without_query_is_an_error() {
+        let result = ConfigBuilder::new().add_file("poem.txt").build();
+        assert_eq!(Err(String::from("a query is required")), result.map(|_| ()));
+    }
+
+    #[test]
+    fn regex_anchored_pattern() {
+        let contents = "\
+foo at the start
+not foo at the start
+food";
+        assert_eq!(
+            vec!["foo at the start", "food"],
+            search_regex("^foo", contents).unwrap()
+        );
+    }
+
+    #[test]
+    fn regex_character_class() {
+        let contents = "\
+gray
+grey
+groy
+gr3y";
+        assert_eq!(
+            vec!["gray", "grey"],
+            search_regex("gr[ae]y", contents).unwrap()
+        );
+        assert_eq!(vec!["gr3y"], search_regex(r"gr\dy", contents).unwrap());
+    }
+
+    #[test]
+    fn regex_flag_matches_lines_through_config() {
+        let config = config(&["--regex", "^(How|To)", "poem.txt"]);
+        assert_eq!(MatchMode::Regex, config.match_mode);
+        let contents = "\
+How dreary to be somebody!
+Then there's a pair of us
+To tell your name";
+        assert_eq!(
+            vec!["How dreary to be somebody!", "To tell your name"],
+            matching_lines(&config, contents)
+        );
+    }
+
+    #[test]
+    fn malformed_regex_is_an_error() {
+        assert!(search_regex("(unclosed", "anything").is_err());
+
+        let args = vec!["minigrep", "--regex", "(unclosed", "poem.txt"];
+        let result = Config::new(args.into_iter().map(String::from));
+        assert_eq!(
+            Err("the query given with --regex is not a valid regular expression"),
+            result.map(|_| ())
+        );
+
+        let result = ConfigBuilder::new()
+            .query("[a-")
+            .match_mode(MatchMode::Regex)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn literal_mode_does_not_interpret_the_query() {
+        let config = config(&["a.c", "poem.txt"]);
+        assert_eq!(MatchMode::Literal, config.match_mode);
+        assert_eq!(vec!["a.c"], matching_lines(&config, "abc\na.c"));
+    }
+
+    #[test]
+    fn search_text_line_numbers_case_insensitive_invert() {
+        let config = ConfigBuilder::new()
+            .query("rUsT")
+            .line_numbers(true)
+            .case_insensitive(true)
+            .invert(true)
+            .build()
+            .unwrap();
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+        assert_eq!(
+            vec!["2:safe, fast, productive.", "3:Pick three."],
+            search_text(&config, contents)
+        );
+    }
+
+    #[test]
+    fn search_text_context_with_line_numbers() {
+        let config = ConfigBuilder::new()
+            .query("match")
+            .line_numbers(true)
+            .context(1)
+            .build()
+            .unwrap();
+        let contents = "\
+one
+match
+two
+three
+four
+match";
+        assert_eq!(
+            vec!["1:one", "2:match", "3:two", "--", "5:four", "6:match"],
+            search_text(&config, contents)
+        );
+    }
+
+    #[test]
+    fn search_text_count_with_invert() {
+        let config = ConfigBuilder::new()
+            .query("three")
+            .count_only(true)
+            .invert(true)
+            .build()
+            .unwrap();
+        assert_eq!(vec!["2"], search_text(&config, "one\ntwo\nthree"));
+    }
+
+    #[test]
+    fn output_lines_name_the_file() {
+        let config = ConfigBuilder::new().query("two").build().unwrap();
+        assert_eq!(
+            vec!["a.txt:two"],
+            output_lines(&config, Some("a.txt"), "one\ntwo\nthree")
+        );
+    }
+
+    #[test]
+    fn replace_single_occurrence() {
+        assert_eq!(
+            "How public, like a toad",
+            replace_in_line("frog", "How public, like a frog", "toad", false)
+        );
+    }
+
+    #[test]
+    fn replace_multiple_occurrences_per_line() {
+        assert_eq!(
+            "a dog, a dog, and a dogfish",
+            replace_in_line("cat", "a cat, a cat, and a catfish", "dog", false)
+        );
+    }
+
+    #[test]
+    fn replace_case_insensitive_keeps_surrounding_text() {
+        assert_eq!(
+            "[Oxide]: safe, [Oxide]y and T[Oxide]",
+            replace_in_line("rust", "Rust: safe, RUSTy and Trust", "[Oxide]", true)
+        );
+        assert_eq!(
+            "Rust: safe",
+            replace_in_line("rust", "Rust: safe", "x", false)
+        );
+    }
+
+    #[test]
+    fn replace_flag_rewrites_reported_lines() {
+        let config = config(&["--replace", "toad", "frog", "poem.txt"]);
+        let contents = "How public, like a frog\nTo tell your name";
+        assert_eq!(
+            vec!["How public, like a toad"],
+            search_text(&config, contents)
+        );
+        assert_eq!(
+            "How public, like a toad\nTo tell your name\n",
+            replace_text(&config, contents)
+        );
+    }
+
+    #[test]
+    fn output_flag_is_parsed_without_replace() {
+        let config = config(&["-o", "out.txt", "frog", "poem.txt"]);
+        assert_eq!(Some(String::from("out.txt")), config.output);
+        assert_eq!(None, config.replace);
+    }
+
+    #[test]
+    fn output_to_a_missing_directory_is_an_error() {
+        let config = config(&["-o", "no-such-dir/out.txt", "nobody", "poem.txt"]);
+        let error = run(config).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("could not write to no-such-dir/out.txt"));
+    }
+
+    #[test]
+    fn max_flag_is_parsed() {
+        let config = config(&["--max", "2", "to", "poem.txt"]);
+        assert_eq!(Some(2), config.max_results);
+        assert_eq!(None, self::config(&["to", "poem.txt"]).max_results);
+
+        let args = vec!["minigrep", "--max", "many", "to", "poem.txt"];
+        let result = Config::new(args.into_iter().map(String::from));
+        assert_eq!(
+            Err("--max expects a non-negative number of matches"),
+            result.map(|_| ())
+        );
+    }
+
+    #[test]
+    fn max_smaller_than_number_of_matches() {
+        let config = ConfigBuilder::new()
+            .query("match")
+            .line_numbers(true)
+            .max_results(2)
+            .build()
+            .unwrap();
+        let contents = "match one\nnope\nmatch two\nmatch three";
+        assert_eq!(
+            vec!["1:match one", "3:match two"],
+            search_text(&config, contents)
+        );
+        assert_eq!(2, count_matches(&config, contents));
+    }
+
+    #[test]
+    fn max_equal_to_number_of_matches() {
+        let config = ConfigBuilder::new()
+            .query("match")
+            .max_results(2)
+            .build()
+            .unwrap();
+        let contents = "match one\nnope\nmatch two";
+        assert_eq!(
+            vec!["match one", "match two"],
+            matching_lines(&config, contents)
+        );
+    }
+
+    #[test]
+    fn max_larger_than_number_of_matches() {
+        let config = ConfigBuilder::new()
+            .query("match")
+            .max_results(10)
+            .build()
+            .unwrap();
+        let contents = "match one\nnope\nmatch two";
+        assert_eq!(
+            vec!["match one", "match two"],
+            search_text(&config, contents)
+        );
+    }
+
+    #[test]
+    fn max_with_context_keeps_trailing_context() {
+        let config = ConfigBuilder::new()
+            .query("match")
+            .context(1)
+            .max_results(1)
+            .build()
+            .unwrap();
+        let contents = "one\nmatch\ntwo\nthree\nfour\nmatch";
+        assert_eq!(vec!["one", "match", "two"], search_text(&config, contents));
+    }
+
+    #[test]
+    fn contains_word_needs_word_boundaries() {
+        assert!(contains_word("the cat sat", "cat"));
+        assert!(!contains_word("category", "cat"));
+        assert!(!contains_word("bobcat", "cat"));
+        assert!(contains_word("bobcat and cat", "cat"));
+    }
+
+    #[test]
+    fn contains_word_at_line_start_and_end() {
+        assert!(contains_word("cat sat", "cat"));
+        assert!(contains_word("the cat", "cat"));
+        assert!(contains_word("cat", "cat"));
+        assert!(contains_word("(cat)", "cat"));
+    }
+
+    #[test]
+    fn word_flag_is_parsed() {
+        let config = config(&["-w", "cat", "poem.txt"]);
+        assert!(config.word_match);
+        let contents = "the cat sat\ncategory\nbobcat\nCat food";
+        assert_eq!(vec!["the cat sat"], matching_lines(&config, contents));
+
+        let config = ConfigBuilder::new()
+            .query("cat")
+            .word_match(true)
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            vec!["the cat sat", "Cat food"],
+            matching_lines(&config, contents)
+        );
+    }
+
+    #[test]
+    fn word_flag_with_replace_only_rewrites_whole_words() {
+        let config = config(&["-w", "--replace", "dog", "cat", "poem.txt"]);
+        assert_eq!("bobcat and dog", config.replace_matches("bobcat and cat"));
+        assert_eq!(
+            vec!["dog, category, dog_food"],
+            search_text(&config, "cat, category, cat_food")
+        );
+    }
+
+    #[test]
+    fn word_flag_with_color_only_highlights_whole_words() {
+        let config = config(&["-w", "--color", "cat", "poem.txt"]);
+        assert_eq!(
+            "bobcat and \x1b[31mcat\x1b[0m",
+            config.render(1, "bobcat and cat")
+        );
+        let coloring = ConfigBuilder::new()
+            .query("cat")
+            .add_file("poem.txt")
+            .case_insensitive(true)
+            .word_match(true)
+            .color(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "\x1b[31mCat\x1b[0m in the Category",
+            coloring.render(1, "Cat in the Category")
+        );
+    }
+
+    #[test]
+    fn word_flag_with_regex_replace_and_color() {
+        let replacing = config(&["-w", "--regex", "--replace", "[$0]", "ca.", "poem.txt"]);
+        assert_eq!(
+            "bobcat [cab] category",
+            replacing.replace_matches("bobcat cab category")
+        );
+
+        let coloring = config(&["-w", "--regex", "--color", "ca.", "poem.txt"]);
+        assert_eq!(
+            "bobcat \x1b[31mcab\x1b[0m category",
+            coloring.render(1, "bobcat cab category")
+        );
+    }
+
+    #[test]
+    fn word_flag_treats_underscore_as_a_boundary_in_both_modes() {
+        let contents = "cat_food\ncatfood";
+        assert!(contains_word("cat_food", "cat"));
+        let literal = config(&["-w", "cat", "poem.txt"]);
+        let regex = config(&["-w", "--regex", "cat", "poem.txt"]);
+        assert_eq!(vec!["cat_food"], matching_lines(&literal, contents));
+        assert_eq!(vec!["cat_food"], matching_lines(&regex, contents));
+    }
+
+    #[test]
+    fn word_flag_with_regex() {
+        let config = config(&["-w", "--regex", "ca.", "poem.txt"]);
+        let contents = "the cat sat\ncategory\nbobcat";
+        assert_eq!(vec!["the cat sat"], matching_lines(&config, contents));
+    }
+
+    #[test]
+    fn run_collect_returns_the_lines() {
+        let path =
+            std::env::temp_dir().join(format!("minigrep_collect_{}.txt", std::process::id()));
+        fs::write(&path, "Rust:\nsafe, fast, productive.\nTrust me.\n").unwrap();
+        let filename = path.to_str().unwrap();
+
+        let single = run_collect(&config(&["-n", "ust", filename]));
+        let twice = run_collect(&config(&["-c", "ust", filename, filename]));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(vec!["1:Rust:", "3:Trust me."], single.unwrap());
+        assert_eq!(
+            vec![format!("{}: 2", filename), format!("{}: 2", filename)],
+            twice.unwrap()
+        );
+    }
+
+    #[test]
+    fn run_collect_missing_file_is_an_error() {
+        assert!(run_collect(&config(&["frog", "no-such-file.txt"])).is_err());
+    }
+
+    #[test]
+    fn max_with_context_stops_reading_after_the_trailing_context() {
+        let contents = "one\nmatch\ntwo\nthree\nmatch\nfour";
+        let mut seen = Vec::new();
+        let groups = group_with_context(contents, 1, 1, |line| {
+            seen.push(line.to_string());
+            line == "match"
+        });
+        assert_eq!(vec![vec![(1, "one"), (2, "match"), (3, "two")]], groups);
+        // "three" and everything after it is never handed to `is_match`
+        assert_eq!(vec!["one", "match"], seen);
+    }
+
+    #[test]
+    fn builder_output_writes_to_the_file() {
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("minigrep_builder_in_{}.txt", std::process::id()));
+        let output = dir.join(format!("minigrep_builder_out_{}.txt", std::process::id()));
+        fs::write(&input, "one frog\ntwo toads\nthree frogs\n").unwrap();
+
+        let config = ConfigBuilder::new()
+            .query("frog")
+            .add_file(input.to_str().unwrap())
+            .output(output.to_str().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(Some(output.to_str().unwrap()), config.output.as_deref());
+        let result = run(config);
+        let written = fs::read_to_string(&output);
+        fs::remove_file(&input).unwrap();
+        fs::remove_file(&output).unwrap();
+
+        result.unwrap();
+        assert_eq!("one frog\nthree frogs\n", written.unwrap());
+    }
+
+    // builds a `Config` the same way `main` does, from the arguments after the program name
+    fn config(args: &[&str]) -> Config {
+        let args: Vec<String> = std::iter::once("minigrep")
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect();
+        Config::new(args.into_iter()).unwrap()
+    }
 }
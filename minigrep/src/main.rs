@@ -5,10 +5,9 @@ use std::process;
 use minigrep::Config;
 
 fn main() {
-    // call `env::args` and immediately use `collect` to turn iterator into a vector containging all
-    // the values produced by the iterator
-    let args: Vec<String> = env::args().collect();
-    let config = Config::new(&args).unwrap_or_else(|err| {
+    // `env::args` returns an iterator, and we pass ownership of that iterator straight to
+    // `Config::new` rather than collecting it into a vector first
+    let config = Config::new(env::args()).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments: {}", err);
         process::exit(1);
     });
@@ -0,0 +1,40 @@
+// Integration tests use minigrep the way another crate would: only its public API is available, so
+// these go through `Config::new` and `run` just like `main` does.
+use std::fs;
+use std::path::PathBuf;
+
+use minigrep::Config;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("minigrep_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn output_flag_writes_matching_lines_to_a_file() {
+    let input = temp_path("input.txt");
+    let output = temp_path("output.txt");
+    fs::write(
+        &input,
+        "I'm nobody! Who are you?\nAre you nobody, too?\nHow dreary to be somebody!\n",
+    )
+    .unwrap();
+
+    let args = vec![
+        "minigrep",
+        "-o",
+        output.to_str().unwrap(),
+        "nobody",
+        input.to_str().unwrap(),
+    ];
+    let config = Config::new(args.into_iter().map(String::from)).unwrap();
+    let result = minigrep::run(config);
+    let written = fs::read_to_string(&output);
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&output).unwrap();
+
+    result.unwrap();
+    assert_eq!(
+        "I'm nobody! Who are you?\nAre you nobody, too?\n",
+        written.unwrap()
+    );
+}
@@ -70,6 +70,60 @@ fn no_definite_use_of_option_t() {
     // let sum = x + y;
 }
 
+// We don't always need a `match` to work with the value inside an `Option<T>`. The `Option<T>` enum
+// has a large number of methods that are useful in a variety of situations, and becoming familiar
+// with them will be extremely useful. For example, `map` runs a closure on the value inside a `Some`
+// and wraps the result back up in a `Some`, and leaves a `None` as `None`.
+fn double_if_some(x: Option<i32>) -> Option<i32> {
+    x.map(|n| n * 2)
+}
+
+// Dividing by zero has no valid answer, and neither does `i32::MIN / -1`, which is one more than
+// `i32::MAX`. `checked_div` returns `None` in both cases instead of panicking.
+fn safe_div(a: i32, b: i32) -> Option<i32> {
+    a.checked_div(b)
+}
+
+// When the closure itself returns an `Option<T>`, `map` would give us an `Option<Option<T>>`. The
+// `and_then` method flattens that for us, so we can chain together steps that might each produce
+// no value. Here we divide 100 by the number inside `x`, and get `None` if either `x` is `None` or
+// the number is zero.
+fn chain_example(x: Option<i32>) -> Option<i32> {
+    x.and_then(|n| safe_div(100, n))
+}
+
 fn main() {
     println!("Hello, world!");
+    println!("double_if_some(Some(4)) = {:?}", double_if_some(Some(4)));
+    println!("chain_example(Some(4)) = {:?}", chain_example(Some(4)));
+    println!("chain_example(Some(0)) = {:?}", chain_example(Some(0)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_if_some_doubles_the_value() {
+        assert_eq!(Some(10), double_if_some(Some(5)));
+        assert_eq!(None, double_if_some(None));
+    }
+
+    #[test]
+    fn safe_div_by_zero_is_none() {
+        assert_eq!(Some(3), safe_div(7, 2));
+        assert_eq!(None, safe_div(7, 0));
+    }
+
+    #[test]
+    fn safe_div_overflow_is_none() {
+        assert_eq!(None, safe_div(i32::MIN, -1));
+    }
+
+    #[test]
+    fn chain_example_chains_the_division() {
+        assert_eq!(Some(25), chain_example(Some(4)));
+        assert_eq!(None, chain_example(Some(0)));
+        assert_eq!(None, chain_example(None));
+    }
 }
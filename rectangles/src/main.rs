@@ -69,6 +69,52 @@ fn main_rectangle_structs() {
         area_structs(&rect1)
     );
     println!("rect1 is {:?}", rect1);
+    println!("The checked area of rect1 is {:?}", checked_area(&rect1));
+
+    match Rectangle::new(20, 20) {
+        Ok(rect2) => println!(
+            "rect2 has a perimeter of {} pixels, is it a square? {}",
+            rect2.perimeter(),
+            rect2.is_square()
+        ),
+        Err(e) => println!("Could not create rect2: {}", e),
+    }
+}
+
+// Functions that belong to `Rectangle` can also live in an `impl` block, which the
+// struct_method_syntax crate covers in detail. `new` checks its arguments before creating the
+// instance: a rectangle with a width or height of zero isn't much of a rectangle, so it returns a
+// `Result` and the caller has to handle the `Err` case.
+impl Rectangle {
+    fn new(width: u32, height: u32) -> Result<Rectangle, String> {
+        if width == 0 || height == 0 {
+            return Err(format!(
+                "a rectangle needs a non-zero width and height, got {}x{}",
+                width, height
+            ));
+        }
+
+        Ok(Rectangle { width, height })
+    }
+
+    // Like `checked_area` below, this uses checked arithmetic: the perimeter only fits in a u32
+    // while the width and height add up to at most `u32::MAX / 2`, and past that it panics with a
+    // clear message instead of wrapping around to a wrong answer
+    fn perimeter(&self) -> u32 {
+        self.width
+            .checked_add(self.height)
+            .and_then(|sum| sum.checked_mul(2))
+            .unwrap_or_else(|| {
+                panic!(
+                    "the perimeter of a {}x{} rectangle overflows a u32",
+                    self.width, self.height
+                )
+            })
+    }
+
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
 }
 
 // this function is now defined with one parameter, whose type is a immutable borrow of the struct
@@ -76,3 +122,63 @@ fn main_rectangle_structs() {
 fn area_structs(rectangle: &Rectangle) -> u32 {
     rectangle.width * rectangle.height
 }
+
+// Multiplying two large u32 values can overflow. In a debug build that panics, and in a release build
+// the result silently wraps around to a much smaller number. `checked_mul` returns `None` instead of
+// overflowing, so this version can tell the caller the area doesn't fit in a u32.
+fn checked_area(r: &Rectangle) -> Result<u32, String> {
+    r.width.checked_mul(r.height).ok_or_else(|| {
+        format!(
+            "the area of a {}x{} rectangle is too large for a u32",
+            r.width, r.height
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_area_of_a_normal_rectangle() {
+        let rect = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        assert_eq!(Ok(1500), checked_area(&rect));
+    }
+
+    #[test]
+    fn checked_area_detects_overflow() {
+        let rect = Rectangle {
+            width: u32::MAX,
+            height: 2,
+        };
+        assert!(checked_area(&rect).is_err());
+    }
+
+    #[test]
+    fn new_rectangle_area_and_perimeter() {
+        let rect = Rectangle::new(30, 50).unwrap();
+        assert_eq!(1500, area_structs(&rect));
+        assert_eq!(160, rect.perimeter());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows a u32")]
+    fn perimeter_too_large_for_a_u32_panics() {
+        Rectangle::new(u32::MAX, 1).unwrap().perimeter();
+    }
+
+    #[test]
+    fn is_square() {
+        assert!(Rectangle::new(7, 7).unwrap().is_square());
+        assert!(!Rectangle::new(7, 8).unwrap().is_square());
+    }
+
+    #[test]
+    fn new_rejects_zero_dimensions() {
+        assert!(Rectangle::new(0, 5).is_err());
+        assert!(Rectangle::new(5, 0).is_err());
+    }
+}
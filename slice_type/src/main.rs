@@ -6,6 +6,8 @@ fn main() {
     call_first_word();
     call_string_sices_as_parameters();
     other_slices();
+    println!("Words: {:?}", words("  hello   world  "));
+    println!("Second word: {:?}", nth_word("hello slice world", 1));
 }
 
 // Another data type that not have ownership is the slice. Slices let you reference a contiguous
@@ -181,6 +183,37 @@ fn first_word_rewrite2(s: &str) -> &str {
     &s[..]
 }
 
+// `first_word_rewrite2` only looks for spaces, so a tab or a newline would end up inside a word. To
+// collect every word in the string we want to split on any whitespace, and `split_whitespace` does
+// exactly that: it also skips consecutive, leading, and trailing whitespace, so we never get empty
+// words. None of the words are copied: each `&str` in the returned `Vec` points into `s`.
+fn words(s: &str) -> Vec<&str> {
+    s.split_whitespace().collect()
+}
+
+// The word at index `n` (counting from zero), or `None` if the string doesn't have that many words
+fn nth_word(s: &str, n: usize) -> Option<&str> {
+    words(s).get(n).copied()
+}
+
+// `first_word_rewrite2` returns `""` for an empty string, and for "  leading" it returns `""` too,
+// because the first space comes before any word. Returning an `Option` says plainly when there is
+// no first word. The standard library's `split_whitespace` skips any leading whitespace (tabs and
+// newlines as well as spaces) and yields each word as a slice of `s`, so the first word is simply
+// the first item it gives us, and `None` if it gives us nothing.
+#[allow(dead_code)]
+fn first_word_opt(s: &str) -> Option<&str> {
+    s.split_whitespace().next()
+}
+
+// The last word works the same way from the other end: `next_back` takes the final item, so any
+// trailing whitespace is skipped. Like `first_word_rewrite2`, this returns a plain `&str`, with `""`
+// standing for "no word at all".
+#[allow(dead_code)]
+fn last_word(s: &str) -> &str {
+    s.split_whitespace().next_back().unwrap_or("")
+}
+
 fn other_slices() {
     let a = [1, 2, 3, 4, 5];
     // To refer to part of an array do this. This slice has a type of `&[i32]`. It works the same
@@ -188,3 +221,189 @@ fn other_slices() {
     let slice = &a[1..3];
     println!("Slice of Array: {:?}", slice);
 }
+
+// Slices work for any contiguous sequence, not just strings. Here is a small run-length encoder over
+// a byte slice: each run of the same byte becomes a `(byte, run_length)` pair. The length is stored
+// in a `u8`, so a run longer than 255 is split into several pairs.
+#[allow(dead_code)]
+fn rle_compress(data: &[u8]) -> Vec<(u8, u8)> {
+    let mut pairs: Vec<(u8, u8)> = Vec::new();
+
+    for &byte in data {
+        match pairs.last_mut() {
+            Some((last, count)) if *last == byte && *count < u8::MAX => *count += 1,
+            _ => pairs.push((byte, 1)),
+        }
+    }
+
+    pairs
+}
+
+// The inverse of `rle_compress`: expand every `(byte, run_length)` pair back into a run of bytes.
+// Newer versions of Rust have `iter::repeat_n` for this, which Clippy suggests, but `repeat` and
+// `take` work on the older compilers these examples are written for.
+#[allow(dead_code)]
+#[allow(clippy::manual_repeat_n)]
+fn rle_decompress(pairs: &[(u8, u8)]) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for &(byte, count) in pairs {
+        data.extend(std::iter::repeat(byte).take(count as usize));
+    }
+
+    data
+}
+
+// A mutable slice, `&mut [T]`, lets a function rearrange the elements of an array or vector in place
+// without taking ownership of it. `swap_first_last` swaps the two ends; a slice with fewer than two
+// elements has nothing to swap.
+#[allow(dead_code)]
+fn swap_first_last<T>(slice: &mut [T]) {
+    if slice.len() > 1 {
+        let last = slice.len() - 1;
+        slice.swap(0, last);
+    }
+}
+
+// `rotate_left_by` moves every element `k` places to the left, and the ones that fall off the front
+// go around to the back, so `[1, 2, 3, 4, 5]` rotated by 2 is `[3, 4, 5, 1, 2]`. Rotating by the
+// length of the slice changes nothing, so only `k % len` matters. The trick is that reversing the
+// first `k` elements, then the rest, then the whole slice leaves everything rotated, and each of
+// those steps works on a smaller slice made with `split_at_mut` or on the slice itself.
+#[allow(dead_code)]
+fn rotate_left_by<T>(slice: &mut [T], k: usize) {
+    if slice.is_empty() {
+        return;
+    }
+    let k = k % slice.len();
+
+    let (front, back) = slice.split_at_mut(k);
+    front.reverse();
+    back.reverse();
+    slice.reverse();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_skips_extra_spaces() {
+        assert_eq!(vec!["hello", "world"], words("  hello   world  "));
+    }
+
+    #[test]
+    fn first_word_opt_finds_a_word() {
+        assert_eq!(Some("hello"), first_word_opt("hello world"));
+        assert_eq!(Some("leading"), first_word_opt("  leading"));
+    }
+
+    #[test]
+    fn first_word_opt_without_a_word() {
+        assert_eq!(None, first_word_opt(""));
+        assert_eq!(None, first_word_opt("   "));
+    }
+
+    #[test]
+    fn last_word_skips_trailing_whitespace() {
+        assert_eq!("world", last_word("hello world"));
+        assert_eq!("word", last_word("word  "));
+        assert_eq!("word", last_word("  word"));
+    }
+
+    #[test]
+    fn last_word_of_empty_string() {
+        assert_eq!("", last_word(""));
+        assert_eq!("", last_word("   "));
+    }
+
+    #[test]
+    fn words_splits_on_tabs_and_newlines() {
+        assert_eq!(vec!["a", "b", "c"], words("a\tb\nc"));
+    }
+
+    #[test]
+    fn words_of_empty_string() {
+        assert!(words("").is_empty());
+        assert!(words("   ").is_empty());
+    }
+
+    #[test]
+    fn nth_word_past_the_end() {
+        let s = "  hello   world  ";
+        assert_eq!(Some("hello"), nth_word(s, 0));
+        assert_eq!(Some("world"), nth_word(s, 1));
+        assert_eq!(None, nth_word(s, 2));
+    }
+
+    #[test]
+    fn rle_round_trip() {
+        let data = b"aaabccdddd";
+        let pairs = rle_compress(data);
+        assert_eq!(vec![(b'a', 3), (b'b', 1), (b'c', 2), (b'd', 4)], pairs);
+        assert_eq!(data.to_vec(), rle_decompress(&pairs));
+    }
+
+    #[test]
+    fn rle_splits_runs_longer_than_255() {
+        let data = vec![7u8; 300];
+        let pairs = rle_compress(&data);
+        assert_eq!(vec![(7, 255), (7, 45)], pairs);
+        assert_eq!(data, rle_decompress(&pairs));
+    }
+
+    #[test]
+    fn rle_empty_input() {
+        assert!(rle_compress(&[]).is_empty());
+        assert!(rle_decompress(&[]).is_empty());
+    }
+
+    #[test]
+    fn swap_first_last_various_lengths() {
+        let mut empty: [i32; 0] = [];
+        swap_first_last(&mut empty);
+        assert_eq!([0; 0], empty);
+
+        let mut one = [1];
+        swap_first_last(&mut one);
+        assert_eq!([1], one);
+
+        let mut two = [1, 2];
+        swap_first_last(&mut two);
+        assert_eq!([2, 1], two);
+
+        let mut five = vec![1, 2, 3, 4, 5];
+        swap_first_last(&mut five);
+        assert_eq!(vec![5, 2, 3, 4, 1], five);
+    }
+
+    #[test]
+    fn rotate_left_by_various_lengths() {
+        let mut empty: [i32; 0] = [];
+        rotate_left_by(&mut empty, 3);
+        assert_eq!([0; 0], empty);
+
+        let mut one = [1];
+        rotate_left_by(&mut one, 3);
+        assert_eq!([1], one);
+
+        let mut five = [1, 2, 3, 4, 5];
+        rotate_left_by(&mut five, 2);
+        assert_eq!([3, 4, 5, 1, 2], five);
+
+        let mut unchanged = [1, 2, 3];
+        rotate_left_by(&mut unchanged, 0);
+        assert_eq!([1, 2, 3], unchanged);
+    }
+
+    #[test]
+    fn rotate_left_by_more_than_the_length() {
+        let mut a = [1, 2, 3, 4, 5];
+        rotate_left_by(&mut a, 7);
+        assert_eq!([3, 4, 5, 1, 2], a);
+
+        let mut b = [1, 2, 3];
+        rotate_left_by(&mut b, 3);
+        assert_eq!([1, 2, 3], b);
+    }
+}
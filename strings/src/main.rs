@@ -119,6 +119,163 @@ fn cat_mult_strings_println_macro() {
     println!("{}", s);
 }
 
+// Slicing Strings
+//
+// Indexing into a string is often a bad idea because it's not clear what the return type of the
+// string-indexing operation should be: a byte value, a character, a grapheme cluster, or a string
+// slice. We can use `[]` with a range to create a string slice containing particular bytes, but the
+// range is in bytes, not characters. Each of the characters in "Здравствуйте" takes 2 bytes, so
+// `&hello[0..4]` is "Зд", and `&hello[0..1]` would panic at runtime because byte 1 is in the middle
+// of a character, just like accessing an invalid index in a vector.
+//
+// If we want to slice by characters instead, we can use the `char_indices` method, which gives us
+// each `char` along with the byte index it starts at. That lets us turn character positions into
+// byte positions that are always on a character boundary. This function returns the characters from
+// `start` up to (but not including) `end`, or `None` if the range doesn't fit in the string.
+fn char_slice(s: &str, start: usize, end: usize) -> Option<&str> {
+    if start > end {
+        return None;
+    }
+
+    // The byte index where each character starts, followed by the length of the string so that a
+    // range can end right after the last character
+    let mut byte_indices = s.char_indices().map(|(i, _)| i).chain(Some(s.len()));
+    let start_byte = byte_indices.nth(start)?;
+    let end_byte = if end == start {
+        start_byte
+    } else {
+        byte_indices.nth(end - start - 1)?
+    };
+
+    Some(&s[start_byte..end_byte])
+}
+
+fn slice_by_chars() {
+    let hello = "Здравствуйте";
+    println!("First two bytes: {}", &hello[0..4]);
+    println!("First two chars: {:?}", char_slice(hello, 0, 2));
+}
+
+// Methods for Iterating Over Strings
+//
+// The best way to operate on pieces of strings is to be explicit about whether you want characters or
+// bytes. The `chars` method gives us each Unicode scalar value as a `char`, so reversing a string
+// with `chars().rev()` keeps multibyte characters like '😻' intact, where reversing the bytes would
+// produce invalid UTF-8.
+//
+// Note that this reverses scalar values, not grapheme clusters (what a person would call a letter).
+// A letter written as a base character followed by a combining mark, like "e\u{301}" for "é", is two
+// scalar values, so after reversing the accent ends up attached to the character that used to come
+// before it. Getting grapheme clusters from strings is complex, so this functionality is not provided
+// by the standard library.
+fn reverse_chars(s: &str) -> String {
+    s.chars().rev().collect()
+}
+
+// A palindrome reads the same forwards and backwards once case, spaces and punctuation are ignored,
+// like "A man, a plan, a canal: Panama". We keep only the alphanumeric `char`s, lowercased, and then
+// compare the `char`s from the front with the `char`s from the back. As with `reverse_chars`, this
+// works with scalar values, so letters like 'É' are compared whole rather than byte by byte.
+fn is_palindrome(s: &str) -> bool {
+    let chars: Vec<char> = s
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect();
+    chars.iter().eq(chars.iter().rev())
+}
+
+// Convert strings to pig latin. The first consonant of each word is moved to the end of the word and
+// "ay" is added, so "first" becomes "irst-fay". Words that start with a vowel have "hay" added to the
+// end instead ("apple" becomes "apple-hay"). Vowels are checked without caring about case, and any
+// word that doesn't start with a letter, like "42", is left as it is. The words in the result are
+// separated by single spaces.
+//
+// Slicing with `&word[first.len_utf8()..]` is safe here because we slice right after the first
+// `char`, which is always on a character boundary.
+fn to_pig_latin(text: &str) -> String {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            let first = match word.chars().next() {
+                Some(c) if c.is_alphabetic() => c,
+                _ => return word.to_string(),
+            };
+
+            if "aeiou".contains(first.to_ascii_lowercase()) {
+                format!("{}-hay", word)
+            } else {
+                format!("{}-{}ay", &word[first.len_utf8()..], first)
+            }
+        })
+        .collect();
+
+    words.join(" ")
+}
+
+// Convert text to title case: the first letter of each word is uppercased, except for "small words"
+// like "of" and "the", which stay lowercase unless they start the text. `title_case` uses the
+// `SMALL_WORDS` below; `title_case_with` takes the list of small words as a parameter so a caller can
+// pick their own. Like `to_pig_latin`, the words in the result are separated by single spaces.
+//
+// Uppercasing a `char` can produce more than one `char` (the German 'ß' becomes "SS"), which is why
+// `to_uppercase` returns an iterator that we collect into the `String` instead of a single `char`.
+const SMALL_WORDS: &[&str] = &["a", "an", "the", "of", "and"];
+
+fn title_case(text: &str) -> String {
+    title_case_with(text, SMALL_WORDS)
+}
+
+fn title_case_with(text: &str, small_words: &[&str]) -> String {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| {
+            if i > 0 && small_words.contains(&word.to_lowercase().as_str()) {
+                return word.to_lowercase();
+            }
+
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    words.join(" ")
+}
+
+// Split one line of CSV into its fields. Fields are separated by commas, but a field wrapped in
+// double quotes can contain commas of its own, and a double quote inside a quoted field is written
+// as two double quotes (`""`). So the line `a,"b, c","say ""hi"""` has the three fields `a`, `b, c`
+// and `say "hi"`.
+//
+// We can't just `split(',')` because of the quoted commas, so we walk the line one `char` at a time
+// and keep track of whether we're inside quotes. `peekable` lets us look at the `char` after a quote
+// to tell an escaped quote from the end of the quoted part of the field.
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
 fn main() {
     create_string_with_new();
     init_string_with_to_string();
@@ -127,4 +284,137 @@ fn main() {
     cat_strings_with_plus();
     cat_mult_strings_plus();
     cat_mult_strings_println_macro();
+    slice_by_chars();
+    println!("Reversed: {}", reverse_chars("Hello 😻"));
+    println!(
+        "Is a palindrome: {}",
+        is_palindrome("A man, a plan, a canal: Panama")
+    );
+    println!("Pig latin: {}", to_pig_latin("the first apple"));
+    println!("Title case: {}", title_case("the lord of the rings"));
+    println!(
+        "CSV fields: {:?}",
+        parse_csv_line(r#"a,"b, c","say ""hi""""#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pig_latin_vowel() {
+        assert_eq!("apple-hay", to_pig_latin("apple"));
+        assert_eq!("Apple-hay", to_pig_latin("Apple"));
+    }
+
+    #[test]
+    fn pig_latin_consonant() {
+        assert_eq!("irst-fay", to_pig_latin("first"));
+    }
+
+    #[test]
+    fn pig_latin_sentence() {
+        assert_eq!(
+            "he-tay irst-fay apple-hay is-hay 42",
+            to_pig_latin("  the first   apple is 42 ")
+        );
+    }
+
+    #[test]
+    fn reverse_chars_ascii() {
+        assert_eq!("cba", reverse_chars("abc"));
+    }
+
+    #[test]
+    fn reverse_chars_keeps_multibyte_chars_intact() {
+        assert_eq!("😻ℤa", reverse_chars("aℤ😻"));
+    }
+
+    #[test]
+    fn reverse_chars_empty() {
+        assert_eq!("", reverse_chars(""));
+    }
+
+    #[test]
+    fn char_slice_ascii() {
+        let s = "hello world";
+        assert_eq!(Some("hello"), char_slice(s, 0, 5));
+        assert_eq!(Some("world"), char_slice(s, 6, 11));
+        assert_eq!(Some(""), char_slice(s, 3, 3));
+    }
+
+    #[test]
+    fn char_slice_multibyte() {
+        let s = "aℤb😻c";
+        assert_eq!(Some("ℤ"), char_slice(s, 1, 2));
+        assert_eq!(Some("ℤb😻"), char_slice(s, 1, 4));
+        assert_eq!(Some("😻c"), char_slice(s, 3, 5));
+    }
+
+    #[test]
+    fn char_slice_out_of_range() {
+        let s = "aℤb😻c";
+        assert_eq!(None, char_slice(s, 0, 6));
+        assert_eq!(None, char_slice(s, 6, 6));
+        assert_eq!(None, char_slice(s, 3, 2));
+    }
+
+    #[test]
+    fn title_case_keeps_small_words_lowercase() {
+        assert_eq!("The Lord of the Rings", title_case("the lord of the rings"));
+        assert_eq!("A Tale of Two Cities", title_case("a tale OF two cities"));
+    }
+
+    #[test]
+    fn title_case_single_word() {
+        assert_eq!("Rust", title_case("rust"));
+        assert_eq!("The", title_case("the"));
+    }
+
+    #[test]
+    fn title_case_multibyte_first_char() {
+        assert_eq!(
+            "Éclair And Ötzi",
+            title_case_with("éclair and ötzi", &["of"])
+        );
+    }
+
+    #[test]
+    fn csv_plain_row() {
+        assert_eq!(vec!["a", "b", "", "d"], parse_csv_line("a,b,,d"));
+    }
+
+    #[test]
+    fn csv_quoted_field_with_comma() {
+        assert_eq!(
+            vec!["1", "Smith, John", "42"],
+            parse_csv_line(r#"1,"Smith, John",42"#)
+        );
+    }
+
+    #[test]
+    fn csv_quoted_field_with_escaped_quote() {
+        assert_eq!(vec!["say \"hi\"", "x"], parse_csv_line(r#""say ""hi""",x"#));
+    }
+
+    #[test]
+    fn palindrome_ignores_case_and_punctuation() {
+        assert!(is_palindrome("A man, a plan, a canal: Panama"));
+        assert!(!is_palindrome("race a car"));
+    }
+
+    #[test]
+    fn palindrome_empty_string() {
+        assert!(is_palindrome(""));
+        assert!(is_palindrome(" ,.! "));
+    }
+
+    #[test]
+    fn palindrome_multibyte() {
+        assert!(is_palindrome("Été"));
+        assert!(is_palindrome("ℤ😻aℤ"));
+        // 'é' and 'e' are different characters
+        assert!(!is_palindrome("éte"));
+    }
 }
@@ -38,6 +38,61 @@ impl Rectangle {
     fn can_hold(&self, other: &Rectangle) -> bool {
         self.width > other.width && self.height > other.height
     }
+
+    // Like `can_hold`, but `other` may also be turned 90 degrees, swapping its width and height, if
+    // that's the only way it fits
+    fn can_hold_rotated(&self, other: &Rectangle) -> bool {
+        let rotated = Rectangle {
+            width: other.height,
+            height: other.width,
+        };
+        self.can_hold(other) || self.can_hold(&rotated)
+    }
+
+    // The perimeter only fits in a u32 while the width and height add up to at most `u32::MAX / 2`.
+    // Like `scale`, we use checked arithmetic and panic with a clear message past that, rather than
+    // wrapping around to a wrong answer in a release build.
+    fn perimeter(&self) -> u32 {
+        self.width
+            .checked_add(self.height)
+            .and_then(|sum| sum.checked_mul(2))
+            .unwrap_or_else(|| {
+                panic!(
+                    "the perimeter of a {}x{} rectangle overflows a u32",
+                    self.width, self.height
+                )
+            })
+    }
+
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    // `scale` only reads the instance, so it borrows `self` immutably and returns a brand new
+    // `Rectangle`, leaving the original as it was. `checked_mul` returns `None` instead of wrapping
+    // around when the result doesn't fit in a u32, which we turn into a panic with a clear message.
+    fn scale(&self, factor: u32) -> Rectangle {
+        Rectangle {
+            width: scale_dimension(self.width, factor),
+            height: scale_dimension(self.height, factor),
+        }
+    }
+
+    // `scale_mut` changes the instance it's called on, so it needs `&mut self`, and the variable
+    // holding the `Rectangle` has to be declared with `mut` to call it.
+    fn scale_mut(&mut self, factor: u32) {
+        self.width = scale_dimension(self.width, factor);
+        self.height = scale_dimension(self.height, factor);
+    }
+}
+
+fn scale_dimension(dimension: u32, factor: u32) -> u32 {
+    dimension.checked_mul(factor).unwrap_or_else(|| {
+        panic!(
+            "scaling a dimension of {} by {} overflows a u32",
+            dimension, factor
+        )
+    })
 }
 
 fn main() {
@@ -66,9 +121,37 @@ fn main() {
 
     println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
     println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+    println!(
+        "Can rect1 hold rect3 if it's rotated? {}",
+        rect1.can_hold_rotated(&rect3)
+    );
 
     // to call the associated function we use this syntax instead of method syntax
     println!("{:?}", Rectangle::square(3));
+
+    match Rectangle::new(20, 20) {
+        Ok(rect4) => println!(
+            "rect4 has a perimeter of {} pixels, is it a square? {}",
+            rect4.perimeter(),
+            rect4.is_square()
+        ),
+        Err(e) => println!("Could not create rect4: {}", e),
+    }
+
+    let mut rect5 = rect2.scale(2);
+    println!(
+        "rect2 scaled by 2 is {:?}, rect2 is still {:?}",
+        rect5, rect2
+    );
+    rect5.scale_mut(3);
+    println!("rect5 scaled in place by 3 is {:?}", rect5);
+
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(rect1),
+        Box::new(Circle { radius: 1.0 }),
+        Box::new(Triangle::new(3.0, 4.0, 5.0).unwrap()),
+    ];
+    println!("The shapes cover {:.2} square pixels", total_area(&shapes));
 }
 
 // Another useful feature of `impl` blocks in that we're allowd to define function within `impl`
@@ -90,4 +173,259 @@ impl Rectangle {
             height: size,
         }
     }
+
+    // A constructor can also check its arguments before it creates the instance. A rectangle with a
+    // width or height of zero has no area, so instead of returning a `Rectangle` directly this one
+    // returns a `Result` and the caller has to handle the `Err` case.
+    fn new(width: u32, height: u32) -> Result<Rectangle, String> {
+        if width == 0 || height == 0 {
+            return Err(format!(
+                "a rectangle needs a non-zero width and height, got {}x{}",
+                width, height
+            ));
+        }
+
+        Ok(Rectangle { width, height })
+    }
+}
+
+// Rectangles aren't the only shapes with an area and a perimeter. A trait lets us describe that
+// behavior once and implement it for each kind of shape. The trait's methods return `f64`, since a
+// circle's area is rarely a whole number.
+trait Shape {
+    fn area(&self) -> f64;
+    fn perimeter(&self) -> f64;
+}
+
+// `Rectangle` already has inherent `area` and `perimeter` methods that return a u32. When a type has
+// an inherent method and a trait method with the same name, `rect.area()` calls the inherent one; to
+// call the trait's version on a `Rectangle` we write `Shape::area(&rect)`. The trait's versions turn
+// the width and height into `f64`s first and do the math there, so a rectangle too big for the u32
+// methods still gets an answer instead of overflowing.
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        f64::from(self.width) * f64::from(self.height)
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (f64::from(self.width) + f64::from(self.height))
+    }
+}
+
+#[derive(Debug)]
+struct Circle {
+    radius: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * std::f64::consts::PI * self.radius
+    }
+}
+
+#[derive(Debug)]
+struct Triangle {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Triangle {
+    // Not every three lengths make a triangle: each side has to be shorter than the other two put
+    // together (the "triangle inequality"), so like `Rectangle::new` this returns a `Result`. Every
+    // comparison with NaN is false, so `a <= 0.0` alone would let a NaN side through. `is_finite` is
+    // false for NaN and for infinity, so we check that first and then rule out zero and negatives.
+    fn new(a: f64, b: f64, c: f64) -> Result<Triangle, String> {
+        if [a, b, c]
+            .iter()
+            .any(|side| !side.is_finite() || *side <= 0.0)
+        {
+            return Err(format!(
+                "a triangle needs sides longer than zero, got {}, {} and {}",
+                a, b, c
+            ));
+        }
+        if a + b <= c || a + c <= b || b + c <= a {
+            return Err(format!(
+                "sides of {}, {} and {} don't make a triangle",
+                a, b, c
+            ));
+        }
+
+        Ok(Triangle { a, b, c })
+    }
+}
+
+impl Shape for Triangle {
+    // Heron's formula finds the area from the three sides alone, using half the perimeter `s`
+    fn area(&self) -> f64 {
+        let s = self.perimeter() / 2.0;
+        (s * (s - self.a) * (s - self.b) * (s - self.c)).sqrt()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.a + self.b + self.c
+    }
+}
+
+// A `Vec<Box<dyn Shape>>` can hold rectangles, circles and triangles together, and `area` is looked
+// up for each one at runtime
+fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_rectangle_area_and_perimeter() {
+        let rect = Rectangle::new(30, 50).unwrap();
+        assert_eq!(1500, rect.area());
+        assert_eq!(160, rect.perimeter());
+    }
+
+    #[test]
+    fn perimeter_at_the_u32_limit() {
+        let rect = Rectangle::new(u32::MAX / 2 - 1, 1).unwrap();
+        assert_eq!(u32::MAX - 1, rect.perimeter());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows a u32")]
+    fn perimeter_too_large_for_a_u32_panics() {
+        Rectangle::new(u32::MAX, 1).unwrap().perimeter();
+    }
+
+    #[test]
+    fn is_square() {
+        assert!(Rectangle::new(7, 7).unwrap().is_square());
+        assert!(!Rectangle::new(7, 8).unwrap().is_square());
+    }
+
+    #[test]
+    fn new_rejects_zero_width() {
+        assert!(Rectangle::new(0, 5).is_err());
+    }
+
+    #[test]
+    fn new_rejects_zero_height() {
+        assert!(Rectangle::new(5, 0).is_err());
+    }
+
+    #[test]
+    fn can_hold_rotated_only_after_rotating() {
+        let larger = Rectangle::new(8, 4).unwrap();
+        let smaller = Rectangle::new(3, 7).unwrap();
+        assert!(!larger.can_hold(&smaller));
+        assert!(larger.can_hold_rotated(&smaller));
+    }
+
+    #[test]
+    fn can_hold_rotated_fits_both_ways() {
+        let larger = Rectangle::new(10, 10).unwrap();
+        let smaller = Rectangle::new(3, 7).unwrap();
+        assert!(larger.can_hold(&smaller));
+        assert!(larger.can_hold_rotated(&smaller));
+    }
+
+    #[test]
+    fn can_hold_rotated_fits_neither_way() {
+        let larger = Rectangle::new(8, 4).unwrap();
+        let smaller = Rectangle::new(9, 2).unwrap();
+        assert!(!larger.can_hold_rotated(&smaller));
+    }
+
+    #[test]
+    fn scale_returns_a_new_rectangle() {
+        let rect = Rectangle::new(3, 4).unwrap();
+        let scaled = rect.scale(5);
+
+        assert_eq!(15, scaled.width);
+        assert_eq!(20, scaled.height);
+        // the original is left unchanged
+        assert_eq!(3, rect.width);
+        assert_eq!(4, rect.height);
+    }
+
+    #[test]
+    fn scale_mut_changes_the_receiver() {
+        let mut rect = Rectangle::new(3, 4).unwrap();
+        rect.scale_mut(2);
+
+        assert_eq!(6, rect.width);
+        assert_eq!(8, rect.height);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows a u32")]
+    fn scale_panics_on_overflow() {
+        Rectangle::new(u32::MAX, 1).unwrap().scale(2);
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn rectangle_as_a_shape() {
+        let rect = Rectangle::new(3, 4).unwrap();
+        assert_close(12.0, Shape::area(&rect));
+        assert_close(14.0, Shape::perimeter(&rect));
+    }
+
+    #[test]
+    fn circle_area_and_perimeter() {
+        let circle = Circle { radius: 2.0 };
+        assert_close(4.0 * std::f64::consts::PI, circle.area());
+        assert_close(4.0 * std::f64::consts::PI, circle.perimeter());
+    }
+
+    #[test]
+    fn triangle_area_and_perimeter() {
+        let triangle = Triangle::new(3.0, 4.0, 5.0).unwrap();
+        assert_close(6.0, triangle.area());
+        assert_close(12.0, triangle.perimeter());
+    }
+
+    #[test]
+    fn triangle_new_rejects_impossible_sides() {
+        assert!(Triangle::new(1.0, 2.0, 3.0).is_err());
+        assert!(Triangle::new(1.0, 1.0, 5.0).is_err());
+        assert!(Triangle::new(0.0, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn triangle_new_rejects_nan_and_infinite_sides() {
+        assert!(Triangle::new(f64::NAN, 4.0, 5.0).is_err());
+        assert!(Triangle::new(3.0, f64::NAN, 5.0).is_err());
+        assert!(Triangle::new(f64::INFINITY, f64::INFINITY, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn shape_for_rectangle_does_not_overflow() {
+        let rect = Rectangle::new(u32::MAX, 2).unwrap();
+        assert_close(2.0 * f64::from(u32::MAX), Shape::area(&rect));
+        assert_close(2.0 * (f64::from(u32::MAX) + 2.0), Shape::perimeter(&rect));
+    }
+
+    #[test]
+    fn total_area_of_mixed_shapes() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Rectangle::new(3, 4).unwrap()),
+            Box::new(Circle { radius: 1.0 }),
+            Box::new(Triangle::new(3.0, 4.0, 5.0).unwrap()),
+        ];
+        assert_close(18.0 + std::f64::consts::PI, total_area(&shapes));
+        assert_close(0.0, total_area(&[]));
+    }
 }
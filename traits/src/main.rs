@@ -29,6 +29,13 @@ pub trait Summary {
     //
     // A trait can have multiple methods in its body: the method signatures are list one per line
     // and each line ends with a semicolon
+
+    // A trait can also give a method a body (see "Default Implementations" below). `word_count`
+    // only relies on `summarize`, so every type that implements `Summary` gets it for free.
+    // `split_whitespace` treats any run of spaces as a single separator.
+    fn word_count(&self) -> usize {
+        self.summarize().split_whitespace().count()
+    }
 }
 
 // Implementing a Trait on a Type
@@ -102,17 +109,33 @@ impl Summary2 for NewsArticle {}
 pub trait Summary3 {
     fn summarize_author(&self) -> String;
 
+    fn summarize3_body(&self) -> String;
+
     fn summarize3(&self) -> String {
         format!("(Read more from {}...)", self.summarize_author())
     }
+
+    // a default method can compose more than one required method; implementors supply the body
+    // and the author, and get this combined format for free
+    fn summarize_with_author(&self) -> String {
+        format!(
+            "{} (from {})",
+            self.summarize3_body(),
+            self.summarize_author()
+        )
+    }
 }
 
-// to use the above version of `Summary3` we only need to define `summarize_author` when we implement
-// the trait on a type
+// to use the above version of `Summary3` we only need to define `summarize_author` and
+// `summarize3_body` when we implement the trait on a type
 impl Summary3 for Tweet {
     fn summarize_author(&self) -> String {
         format!("@{}", self.username)
     }
+
+    fn summarize3_body(&self) -> String {
+        self.content.clone()
+    }
 }
 
 // Traits as Parameters
@@ -258,6 +281,52 @@ fn returns_summarizable2(switch: bool) -> impl Summary {
 }
 */
 
+// Using Trait Objects to Mix Types
+//
+// When we do want a value that could be a `NewsArticle` or a `Tweet`, we can use a "trait object"
+// instead. A `Box<dyn Summary>` is a pointer to some value on the heap whose type implements
+// `Summary`; which type it is only gets looked at when the program runs. Because every element has
+// the same type, `Box<dyn Summary>`, one vector can hold articles and tweets together.
+pub fn build_feed() -> Vec<Box<dyn Summary>> {
+    vec![
+        Box::new(NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from(
+                "The Pittsburgh Penguins once again are the best \
+                 hockey team in the NHL.",
+            ),
+        }),
+        Box::new(Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: false,
+        }),
+    ]
+}
+
+// Calling `summarize` on a trait object uses "dynamic dispatch": Rust looks up which type's
+// `summarize` to run at runtime, instead of generating a separate copy of the function for each
+// type at compile time the way it does for generics
+pub fn print_all(items: &[Box<dyn Summary>]) {
+    for item in items {
+        println!("{}", item.summarize());
+    }
+}
+
+// Trait objects don't have to be boxed; a plain reference works too. Compare `notify_all` with
+// `notify2`: for `notify2<T: Summary>` the compiler generates one copy of the function for every
+// concrete `T` it's called with ("monomorphization"), and each call handles a single type. A slice of
+// `&dyn Summary` can hold references to articles and tweets at the same time, and one copy of
+// `notify_all` handles all of them through dynamic dispatch.
+pub fn notify_all(items: &[&dyn Summary]) {
+    for item in items {
+        println!("Breaking news! {}", item.summarize());
+    }
+}
+
 // Fixing the `largest` function with Trait Bounds
 //
 // Now that we know how to specify the behavior you wan to use using the generic type parameter's
@@ -387,6 +456,20 @@ impl<T: Display + PartialOrd> Pair<T> {
     }
 }
 
+// We might be tempted to write a "blanket implementation" like `impl<T: Display> Summary for T`, so
+// that anything that can be printed can also be summarized. But `NewsArticle` and `Tweet` already
+// implement `Summary`, and if either of them ever implemented `Display` Rust couldn't tell which
+// implementation to use, so the compiler rejects that blanket impl. Instead, we can wrap the value
+// in a tuple struct of our own and implement `Summary` on the wrapper for any `T` that is `Display`.
+// The wrapper is a different type from `NewsArticle` and `Tweet`, so there's nothing to conflict.
+pub struct DisplayWrapper<T: Display>(pub T);
+
+impl<T: Display> Summary for DisplayWrapper<T> {
+    fn summarize(&self) -> String {
+        format!("(auto) {}", self.0)
+    }
+}
+
 fn main() {
     let tweet = Tweet {
         username: String::from("horse_ebooks"),
@@ -416,6 +499,11 @@ fn main() {
     };
 
     println!("1 new tweet: {}", tweet2.summarize3());
+    println!("{}", tweet2.summarize_with_author());
+
+    print_all(&build_feed());
+    notify(&DisplayWrapper(42));
+    notify_all(&[&tweet, &article]);
 
     let number_list = vec![34, 50, 25, 100, 65];
 
@@ -427,3 +515,81 @@ fn main() {
     let result = largest(&char_list);
     println!("The largest char is {}", result);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tweet(content: &str) -> Tweet {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from(content),
+            reply: false,
+            retweet: false,
+        }
+    }
+
+    fn article() -> NewsArticle {
+        NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from("The Pittsburgh Penguins once again are the best."),
+        }
+    }
+
+    #[test]
+    fn feed_mixes_articles_and_tweets() {
+        let feed = build_feed();
+        assert_eq!(2, feed.len());
+        assert_eq!(
+            "horse_ebooks: of course, as you probably already know, people",
+            feed[1].summarize()
+        );
+        print_all(&feed);
+    }
+
+    #[test]
+    fn display_wrapper_summarizes_an_i32() {
+        assert_eq!("(auto) 42", DisplayWrapper(42).summarize());
+    }
+
+    #[test]
+    fn display_wrapper_summarizes_a_string() {
+        let wrapper = DisplayWrapper(String::from("hello traits"));
+        assert_eq!("(auto) hello traits", wrapper.summarize());
+    }
+
+    #[test]
+    fn notify_all_mixes_references_to_different_types() {
+        let tweet = tweet("of course, as you probably already know, people");
+        let article = article();
+
+        let items: Vec<&dyn Summary> = vec![&tweet, &article, &tweet];
+        notify_all(&items);
+
+        let summarized: Vec<String> = items.iter().map(|item| item.summarize()).collect();
+        assert_eq!(3, summarized.len());
+        assert_eq!(summarized[0], summarized[2]);
+    }
+
+    #[test]
+    fn summarize_with_author_combines_body_and_author() {
+        let tweet = tweet("of course, as you probably already know, people");
+        assert_eq!(
+            "of course, as you probably already know, people (from @horse_ebooks)",
+            tweet.summarize_with_author()
+        );
+    }
+
+    #[test]
+    fn word_count_of_summaries() {
+        let tweet = tweet("of   course,  as you know");
+        // "horse_ebooks:" "of" "course," "as" "you" "know"
+        assert_eq!(6, tweet.word_count());
+
+        let article = article();
+        // the headline's six words, "by", "Iceburgh" and the three parts of the location
+        assert_eq!(11, article.word_count());
+    }
+}
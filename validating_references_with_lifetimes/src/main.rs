@@ -234,7 +234,61 @@ fn use_important_excerpt() {
 // Lifetime Annotations in Method Definitions
 //
 // When we implement methods on a struct with lifetimes, we use the same syntax as that of generic
-// type parameters. Where we declare and us eht
+// type parameters. Where we declare and us eht lifetime parameters depends on whether they're related
+// to the struct fields or the method parameters and return values.
+//
+// Lifetime names for struct fields always need to be declared after the `impl` keyword and then used
+// after the struct's name, because those lifetimes are part of the struct's type.
+impl<'a> ImportantExcert<'a> {
+    // The only parameter here is a reference to `self`, and the return value is an i32, which is not
+    // a reference to anything, so we don't need any lifetime annotations
+    #[allow(dead_code)]
+    fn level(&self) -> i32 {
+        3
+    }
+
+    // Here is an example where the third lifetime elision rule applies. There are two input lifetimes,
+    // so Rust applies the first lifetime elision rule and gives both `&self` and `announcement` their
+    // own lifetimes. Then, because one of the parameters is `&self`, the return type gets the lifetime
+    // of `&self`, and all lifetimes have been accounted for.
+    fn announce_and_return_part(&self, announcement: &str) -> &str {
+        println!("Attention please: {}", announcement);
+        self.part
+    }
+}
+
+fn use_important_excerpt_method() {
+    let novel = String::from("Call me Ishmael. Some year ago...");
+    let first_sentence = novel.split('.').next().expect("Could not find a '.'");
+    let i = ImportantExcert {
+        part: first_sentence,
+    };
+    let part = i.announce_and_return_part("here is the first sentence");
+    println!("{}", part);
+}
+
+// Generic Type Parameters, Trait Bounds, and Lifetimes Together
+//
+// Let's briefly look at the syntax of specifying generic type parameters, trait bounds, and lifetimes
+// all in one function. This is the `longest` function that returns the longer of two string slices,
+// but now it has an extra parameter named `ann` of the generic type `T`, which can be filled in by
+// any type that implements the `Display` trait as specified by the `where` clause. This extra
+// parameter will be printed using `{}`, which is why the `Display` trait bound is necessary. Because
+// lifetimes are a type of generic, the declarations of the lifetime parameter `'a` and the generic
+// type parameter `T` go in the same list inside the angle brackets after the function name.
+use std::fmt::Display;
+
+fn longest_with_announcement<'a, T>(x: &'a str, y: &'a str, ann: T) -> &'a str
+where
+    T: Display,
+{
+    println!("Announcement! {}", ann);
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
 
 fn main() {
     let string1 = String::from("abcd");
@@ -245,4 +299,41 @@ fn main() {
     example_different_concrete_lifetimes_passed_to_longest();
     // example_result_lifetime_is_smaller_of_two_params_lifetimes();
     use_important_excerpt();
+    use_important_excerpt_method();
+
+    let result = longest_with_announcement(string1.as_str(), string2, "Comparing strings");
+    println!("With Announcement: longest string is {}", result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn announce_and_return_part_returns_the_excerpt() {
+        let novel = String::from("Call me Ishmael. Some year ago...");
+        let first_sentence = novel.split('.').next().unwrap();
+        let excerpt = ImportantExcert {
+            part: first_sentence,
+        };
+
+        assert_eq!(
+            "Call me Ishmael",
+            excerpt.announce_and_return_part("listen up")
+        );
+        assert_eq!(3, excerpt.level());
+    }
+
+    #[test]
+    fn longest_with_an_i32_announcement() {
+        assert_eq!("hello", longest_with_announcement("hello", "hi", 42));
+    }
+
+    #[test]
+    fn longest_with_a_str_announcement() {
+        let string1 = String::from("abcd");
+        let string2 = String::from("xyz12");
+        let result = longest_with_announcement(string1.as_str(), string2.as_str(), "Comparing");
+        assert_eq!("xyz12", result);
+    }
 }
@@ -124,6 +124,263 @@ fn using_enum_for_multi_type_vector() {
         SpreadsheetCell::Float(10.12),
     ];
     println!("Multi-type Vector is: {:?}", row);
+    println!("Sum of the numeric cells is: {}", sum_numeric(&row));
+
+    let row = SpreadsheetRow(row);
+    for cell in &row {
+        println!("Cell: {:?}", cell);
+    }
+    println!("Text cells: {:?}", row.texts());
+}
+
+// Because every element has the same type, we can also put methods on the enum and call them on any
+// element. `as_f64` uses a `match` to read a number out of each kind of cell: integers are converted
+// to floats, and text is parsed, with `ok()` turning a failed parse into `None`.
+impl SpreadsheetCell {
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            SpreadsheetCell::Int(value) => Some(f64::from(*value)),
+            SpreadsheetCell::Float(value) => Some(*value),
+            SpreadsheetCell::Text(text) => text.trim().parse().ok(),
+        }
+    }
+}
+
+// Adds up every cell in the row that holds (or can be read as) a number, skipping the rest
+fn sum_numeric(row: &[SpreadsheetCell]) -> f64 {
+    row.iter().filter_map(|cell| cell.as_f64()).sum()
+}
+
+// Wrapping the vector in a tuple struct gives the row a name of its own and a place for methods.
+// Implementing `IntoIterator` for a reference to the row is what lets us write `for cell in &row`,
+// the same way we loop over `&v` for a plain vector; we just hand out the vector's own iterator.
+struct SpreadsheetRow(Vec<SpreadsheetCell>);
+
+impl SpreadsheetRow {
+    // The contents of just the `Text` cells, in the order they appear in the row
+    fn texts(&self) -> Vec<&str> {
+        self.into_iter()
+            .filter_map(|cell| match cell {
+                SpreadsheetCell::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a SpreadsheetRow {
+    type Item = &'a SpreadsheetCell;
+    type IntoIter = std::slice::Iter<'a, SpreadsheetCell>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+// The standard library's `dedup` method only removes duplicates that sit next to each other in the
+// vector. To remove every repeated element while keeping the order in which values first appeared,
+// we can walk through the vector and remember what we've already kept in a `HashSet`. `insert`
+// returns `false` when the value was already in the set, which is exactly when we want to drop it.
+use std::collections::HashSet;
+use std::hash::Hash;
+
+fn dedup_preserve_order<T: Eq + Hash + Clone>(v: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+
+    for item in v {
+        if seen.insert(item.clone()) {
+            result.push(item);
+        }
+    }
+
+    result
+}
+
+// Given a list of integers, use a vector and return the median (when sorted, the value in the middle
+// position) and mode (the value that occurs most often) of the list.
+//
+// Finding the middle value needs the list to be sorted, so `median` sorts the numbers in place. It
+// takes a mutable slice rather than `&mut Vec<i32>` because sorting doesn't change the length; a
+// `&mut Vec<i32>` is turned into a slice automatically when we call it. With an even number of values
+// there are two middle values, and the median is their average. (Clippy would rather we wrote
+// `is_multiple_of(2)`, but that method only arrived in Rust 1.87.)
+#[allow(clippy::manual_is_multiple_of)]
+fn median(v: &mut [i32]) -> Option<f64> {
+    if v.is_empty() {
+        return None;
+    }
+
+    v.sort();
+    let mid = v.len() / 2;
+    if v.len() % 2 == 0 {
+        Some((f64::from(v[mid - 1]) + f64::from(v[mid])) / 2.0)
+    } else {
+        Some(f64::from(v[mid]))
+    }
+}
+
+// To find the mode we count how many times each value occurs in a `HashMap`, then pick the value with
+// the highest count. When several values are tied for the highest count, the smallest of them is the
+// mode, so the answer doesn't depend on the order the `HashMap` hands its entries back in.
+use std::collections::HashMap;
+
+fn mode(v: &[i32]) -> Option<i32> {
+    let mut counts = HashMap::new();
+    for &value in v {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(value, _)| value)
+}
+
+// A vector already behaves like a stack: `push` adds to the end and `pop` removes from the end. We
+// can wrap one in our own `Stack<T>` type that only offers the stack operations, so a caller can't
+// index into the middle of it.
+#[derive(Debug)]
+struct Stack<T> {
+    items: Vec<T>,
+}
+
+#[allow(dead_code)]
+impl<T> Stack<T> {
+    fn new() -> Stack<T> {
+        Stack { items: Vec::new() }
+    }
+
+    fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    // Look at the top of the stack without removing it
+    fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+// Implementing `FromIterator` is what lets `collect` build a `Stack<T>`, pushing the items in the
+// order the iterator produces them
+use std::iter::FromIterator;
+
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Stack<T> {
+        Stack {
+            items: Vec::from_iter(iter),
+        }
+    }
+}
+
+// Implementing `IntoIterator` lets us use a `Stack<T>` in a `for` loop. The items come out the way
+// `pop` would return them: last in, first out
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = std::iter::Rev<std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter().rev()
+    }
+}
+
+// A vector can hold other vectors. `flatten` turns a vector of vectors into one vector holding all the
+// inner elements, in order, by letting the iterator's own `flatten` adaptor walk through each inner
+// vector in turn.
+fn flatten<T>(nested: Vec<Vec<T>>) -> Vec<T> {
+    nested.into_iter().flatten().collect()
+}
+
+// `chunk` goes roughly the other way: it splits a slice into vectors of `size` elements, the last of
+// which may be shorter. The slice's `chunks` method gives us the pieces as slices, and `to_vec`
+// copies each one into its own vector, which is why `T` has to be `Clone`. A `size` of 0 can't make
+// any progress, so `chunks` panics on it; we check for it first and return no chunks at all.
+fn chunk<T: Clone>(v: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return Vec::new();
+    }
+    v.chunks(size).map(|piece| piece.to_vec()).collect()
+}
+
+// A matrix doesn't need a vector of vectors. `Matrix` keeps all of its numbers in one flat `Vec<f64>`,
+// "row-major", meaning the whole first row comes first, then the second row, and so on. The value
+// at row `r` and column `c` is then at index `r * cols + c`. Keeping `data` private means the only
+// way to change the numbers is through `set`, which checks the position first.
+#[derive(Debug, PartialEq)]
+struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    // A `rows` by `cols` matrix filled with zeros
+    fn new(rows: usize, cols: usize) -> Matrix {
+        Matrix {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    fn index(&self, r: usize, c: usize) -> Option<usize> {
+        if r < self.rows && c < self.cols {
+            Some(r * self.cols + c)
+        } else {
+            None
+        }
+    }
+
+    fn get(&self, r: usize, c: usize) -> Option<f64> {
+        self.index(r, c).map(|i| self.data[i])
+    }
+
+    fn set(&mut self, r: usize, c: usize, value: f64) -> Result<(), String> {
+        match self.index(r, c) {
+            Some(i) => {
+                self.data[i] = value;
+                Ok(())
+            }
+            None => Err(format!(
+                "({}, {}) is outside a {}x{} matrix",
+                r, c, self.rows, self.cols
+            )),
+        }
+    }
+
+    // Multiplying an `n`x`m` matrix by an `m`x`p` one gives an `n`x`p` matrix, where each value is the
+    // sum of the products of a row from `self` and a column from `other`. The number of columns in
+    // `self` has to match the number of rows in `other`, otherwise there's no answer.
+    fn multiply(&self, other: &Matrix) -> Result<Matrix, String> {
+        if self.cols != other.rows {
+            return Err(format!(
+                "can't multiply a {}x{} matrix by a {}x{} matrix",
+                self.rows, self.cols, other.rows, other.cols
+            ));
+        }
+
+        let mut product = Matrix::new(self.rows, other.cols);
+        for r in 0..self.rows {
+            for c in 0..other.cols {
+                product.data[r * other.cols + c] = (0..self.cols)
+                    .map(|k| self.data[r * self.cols + k] * other.data[k * other.cols + c])
+                    .sum();
+            }
+        }
+        Ok(product)
+    }
 }
 
 fn main() {
@@ -135,4 +392,229 @@ fn main() {
     iterate_through_vector();
     iterate_through_mut_vector();
     using_enum_for_multi_type_vector();
+    println!(
+        "Without duplicates: {:?}",
+        dedup_preserve_order(vec![3, 1, 3, 2, 1])
+    );
+
+    let mut numbers = vec![5, 1, 4, 1, 3];
+    println!("The median is: {:?}", median(&mut numbers));
+    println!("The mode is: {:?}", mode(&numbers));
+
+    let flat = flatten(vec![vec![1, 2], vec![3]]);
+    println!("Flattened: {:?}, chunked: {:?}", flat, chunk(&flat, 2));
+
+    let mut identity = Matrix::new(2, 2);
+    identity.set(0, 0, 1.0).unwrap();
+    identity.set(1, 1, 1.0).unwrap();
+    println!("Identity squared: {:?}", identity.multiply(&identity));
+    println!("Top left of the identity: {:?}", identity.get(0, 0));
+
+    let stack: Stack<i32> = (1..=3).collect();
+    for item in stack {
+        println!("Popped off the stack: {}", item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_f64_for_each_kind_of_cell() {
+        assert_eq!(Some(3.0), SpreadsheetCell::Int(3).as_f64());
+        assert_eq!(Some(10.12), SpreadsheetCell::Float(10.12).as_f64());
+        assert_eq!(
+            Some(2.5),
+            SpreadsheetCell::Text(String::from("2.5")).as_f64()
+        );
+        assert_eq!(None, SpreadsheetCell::Text(String::from("blue")).as_f64());
+    }
+
+    #[test]
+    fn sum_numeric_skips_non_numeric_text() {
+        let row = vec![
+            SpreadsheetCell::Int(3),
+            SpreadsheetCell::Text(String::from("blue")),
+            SpreadsheetCell::Float(1.5),
+            SpreadsheetCell::Text(String::from("4")),
+        ];
+        assert_eq!(8.5, sum_numeric(&row));
+    }
+
+    #[test]
+    fn iterate_over_a_borrowed_row() {
+        let row = SpreadsheetRow(vec![
+            SpreadsheetCell::Int(3),
+            SpreadsheetCell::Text(String::from("blue")),
+            SpreadsheetCell::Float(10.12),
+            SpreadsheetCell::Text(String::from("red")),
+        ]);
+
+        let mut count = 0;
+        for cell in &row {
+            if let SpreadsheetCell::Int(value) = cell {
+                assert_eq!(3, *value);
+            }
+            count += 1;
+        }
+        assert_eq!(4, count);
+        assert_eq!(vec!["blue", "red"], row.texts());
+    }
+
+    #[test]
+    fn flatten_nested_vectors() {
+        assert_eq!(vec![1, 2, 3], flatten(vec![vec![1, 2], vec![3]]));
+        assert_eq!(vec![1, 2], flatten(vec![vec![], vec![1], vec![], vec![2]]));
+    }
+
+    #[test]
+    fn chunk_slice() {
+        assert_eq!(
+            vec![vec![1, 2], vec![3, 4], vec![5]],
+            chunk(&[1, 2, 3, 4, 5], 2)
+        );
+        assert!(chunk::<i32>(&[], 2).is_empty());
+    }
+
+    #[test]
+    fn chunk_size_zero_is_empty() {
+        assert!(chunk(&[1, 2, 3], 0).is_empty());
+        assert!(chunk::<i32>(&[], 0).is_empty());
+    }
+
+    #[test]
+    fn chunk_then_flatten_round_trip() {
+        let v = vec![1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(v, flatten(chunk(&v, 3)));
+    }
+
+    fn matrix(rows: usize, cols: usize, values: &[f64]) -> Matrix {
+        let mut m = Matrix::new(rows, cols);
+        for (i, &value) in values.iter().enumerate() {
+            m.set(i / cols, i % cols, value).unwrap();
+        }
+        m
+    }
+
+    #[test]
+    fn matrix_get_and_set() {
+        let mut m = Matrix::new(2, 3);
+        assert_eq!(Some(0.0), m.get(1, 2));
+        m.set(1, 2, 4.5).unwrap();
+        assert_eq!(Some(4.5), m.get(1, 2));
+        assert_eq!(None, m.get(2, 0));
+        assert!(m.set(0, 3, 1.0).is_err());
+    }
+
+    #[test]
+    fn multiply_2x3_by_3x2() {
+        let a = matrix(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b = matrix(3, 2, &[7.0, 8.0, 9.0, 10.0, 11.0, 12.0]);
+        assert_eq!(
+            Ok(matrix(2, 2, &[58.0, 64.0, 139.0, 154.0])),
+            a.multiply(&b)
+        );
+    }
+
+    #[test]
+    fn multiply_dimension_mismatch() {
+        let a = Matrix::new(2, 3);
+        let b = Matrix::new(2, 3);
+        assert_eq!(
+            Err(String::from("can't multiply a 2x3 matrix by a 2x3 matrix")),
+            a.multiply(&b)
+        );
+    }
+
+    #[test]
+    fn dedup_preserve_order_keeps_first_occurrences() {
+        assert_eq!(vec![3, 1, 2], dedup_preserve_order(vec![3, 1, 3, 2, 1]));
+    }
+
+    #[test]
+    fn dedup_preserve_order_strings() {
+        let words = vec![
+            String::from("blue"),
+            String::from("red"),
+            String::from("blue"),
+            String::from("green"),
+            String::from("red"),
+        ];
+        assert_eq!(
+            vec![
+                String::from("blue"),
+                String::from("red"),
+                String::from("green")
+            ],
+            dedup_preserve_order(words)
+        );
+    }
+
+    #[test]
+    fn median_odd_length() {
+        let mut v = vec![5, 1, 4, 2, 3];
+        assert_eq!(Some(3.0), median(&mut v));
+    }
+
+    #[test]
+    fn median_even_length() {
+        let mut v = vec![4, 1, 3, 2];
+        assert_eq!(Some(2.5), median(&mut v));
+    }
+
+    #[test]
+    fn mode_most_frequent_value() {
+        assert_eq!(Some(7), mode(&[1, 7, 3, 7, 2, 7, 1]));
+    }
+
+    #[test]
+    fn mode_tie_picks_smallest_value() {
+        assert_eq!(Some(2), mode(&[5, 2, 5, 2, 9]));
+    }
+
+    #[test]
+    fn median_and_mode_empty() {
+        let mut empty: Vec<i32> = Vec::new();
+        assert_eq!(None, median(&mut empty));
+        assert_eq!(None, mode(&empty));
+    }
+
+    #[test]
+    fn stack_pops_in_reverse_push_order() {
+        let mut stack = Stack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        assert_eq!(3, stack.len());
+        assert_eq!(Some(&3), stack.peek());
+        assert_eq!(Some(3), stack.pop());
+        assert_eq!(Some(2), stack.pop());
+        assert_eq!(Some(1), stack.pop());
+        assert_eq!(None, stack.pop());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn stack_peek_on_empty() {
+        let stack: Stack<i32> = Stack::new();
+        assert_eq!(None, stack.peek());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn stack_collect_from_range() {
+        let mut stack: Stack<i32> = (1..=3).collect();
+        assert_eq!(Some(3), stack.pop());
+
+        let rest: Vec<i32> = stack.into_iter().collect();
+        assert_eq!(vec![2, 1], rest);
+    }
+
+    #[test]
+    fn dedup_preserve_order_empty() {
+        let empty: Vec<i32> = Vec::new();
+        assert!(dedup_preserve_order(empty).is_empty());
+    }
 }